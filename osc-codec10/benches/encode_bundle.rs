@@ -0,0 +1,80 @@
+//! Encoding wide and deeply nested bundles: the single-pass encoder that
+//! back-patches element sizes versus encoding every element into its own `Vec`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use osc_codec10::{encode_bundle, encode_into_vec, encode_message};
+use osc_types10::{Bundle, Message, OscPacket, OscType};
+
+/// The previous encoder: one temporary `Vec` per element, copied into the parent.
+fn encode_bundle_nested_vec(b: &Bundle<'_>) -> Vec<u8> {
+    let mut buf = b"#bundle\0".to_vec();
+    buf.extend_from_slice(&b.timetag.to_bits().to_be_bytes());
+    for packet in &b.packets {
+        let pkt = match packet {
+            OscPacket::Message(msg) => encode_message(msg),
+            OscPacket::Bundle(bundle) => encode_bundle_nested_vec(bundle),
+        };
+        buf.extend_from_slice(&(pkt.len() as i32).to_be_bytes());
+        buf.extend_from_slice(&pkt);
+    }
+    buf
+}
+
+fn message(i: i32) -> Message<'static> {
+    Message::new(
+        "/mixer/channel/fader",
+        vec![
+            OscType::Int(i),
+            OscType::Float(0.75),
+            OscType::String("main"),
+        ],
+    )
+}
+
+/// One bundle holding `width` messages.
+fn wide(width: i32) -> Bundle<'static> {
+    Bundle::with_messages(1, (0..width).map(message).collect())
+}
+
+/// `depth` bundles nested in each other, each holding two messages.
+fn deep(depth: i32) -> Bundle<'static> {
+    let mut bundle = Bundle::with_messages(1, vec![message(0), message(1)]);
+    for level in 1..depth {
+        let mut outer = Bundle::with_messages(1, vec![message(level)]);
+        outer.add_bundle(bundle);
+        outer.add_message(message(-level));
+        bundle = outer;
+    }
+    bundle
+}
+
+fn encode(c: &mut Criterion) {
+    let bundles = [("wide_256", wide(256)), ("deep_32", deep(32))];
+
+    let mut group = c.benchmark_group("encode_bundle");
+    for (name, bundle) in &bundles {
+        assert_eq!(encode_bundle(bundle), encode_bundle_nested_vec(bundle));
+        group.bench_with_input(BenchmarkId::new("back_patch", name), bundle, |b, bundle| {
+            b.iter(|| encode_bundle(black_box(bundle)))
+        });
+        group.bench_with_input(BenchmarkId::new("nested_vec", name), bundle, |b, bundle| {
+            b.iter(|| encode_bundle_nested_vec(black_box(bundle)))
+        });
+        let packet = OscPacket::Bundle(bundle.clone());
+        group.bench_with_input(
+            BenchmarkId::new("reused_vec", name),
+            &packet,
+            |b, packet| {
+                let mut buf = Vec::new();
+                b.iter(|| {
+                    buf.clear();
+                    encode_into_vec(black_box(packet), &mut buf)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, encode);
+criterion_main!(benches);
@@ -1,4 +1,4 @@
-use osc_codec10::{decode_bundle, decode_message};
+use osc_codec10::decode_packet;
 use osc_types10::OscPacket;
 use std::net::UdpSocket;
 
@@ -25,26 +25,21 @@ fn main() -> std::io::Result<()> {
     let mut buf = [0u8; 65536];
     loop {
         let (n, _src) = sock.recv_from(&mut buf)?;
-        let data = &buf[..n];
-        // very simple heuristic: leading byte '/' => message, '#' => bundle
-        match data.first().copied() {
-            Some(b'/') => {
-                let (msg, used) = decode_message(data).expect("decode message");
-                assert_eq!(used, data.len());
+        match decode_packet(&buf[..n]) {
+            Ok(OscPacket::Message(msg)) => {
                 println!("msg {} args={}", msg.address, msg.args.len());
             }
-            Some(b'#') => {
-                let (bundle, _used) = decode_bundle(data).expect("decode bundle");
+            Ok(OscPacket::Bundle(bundle)) => {
                 let (total_messages, total_bundles) = count_items_in_bundle(&bundle);
                 println!(
                     "bundle timetag={} direct_packets={} total_messages={} total_bundles={}",
-                    bundle.timetag,
+                    bundle.timetag.to_bits(),
                     bundle.packets.len(),
                     total_messages,
                     total_bundles
                 );
             }
-            _ => eprintln!("unknown packet"),
+            Err(e) => eprintln!("invalid packet: {e:?}"),
         }
     }
 }
@@ -0,0 +1,554 @@
+//! Field-by-field annotation of encoded packets, for debugging.
+
+use core::fmt;
+use core::ops::Range;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use osc_types10::TimeTag;
+
+use crate::view::{self, ArgView};
+use crate::{
+    get_cstr_4, get_i32, pad4_len, DecodeMode, DecodeOptions, Error, ErrorKind, Limits,
+    BUNDLE_PREFIX,
+};
+
+/// What a [`Span`] of an annotated packet holds.
+#[derive(Debug, Clone)]
+pub enum Field<'a> {
+    /// The `#bundle` string that starts a bundle, NUL terminator included.
+    BundleTag,
+    /// The time tag of a bundle.
+    TimeTag(TimeTag),
+    /// The size prefix of a bundle element.
+    ElementSize(i32),
+    /// The address of a message.
+    Address(&'a str),
+    /// The type tag string of a message, leading `,` included.
+    TypeTags(&'a str),
+    /// The data of an argument; a blob's span includes its size prefix.
+    Argument {
+        /// Index of the argument, counted over the type tags after the leading
+        /// `,` like [`Error::arg_index`].
+        index: usize,
+        /// The decoded argument. Never [`ArgView::Array`]: array brackets have
+        /// no data, the items are annotated one by one.
+        value: ArgView<'a>,
+    },
+    /// String terminators and alignment padding.
+    Padding,
+    /// The raw bytes after the address of a message without a type tag
+    /// string, in [`DecodeMode::Lenient`].
+    Payload(&'a [u8]),
+    /// Bytes after a message that [`DecodeMode::Lenient`] ignores.
+    Ignored,
+    /// Bytes that could not be decoded, and why.
+    ///
+    /// The error's offset is where decoding went wrong. It is usually the
+    /// start of the span, but points back into the type tag string when the
+    /// data cannot be read because of its type tag.
+    Invalid(Error),
+}
+
+impl fmt::Display for Field<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Field::BundleTag => f.write_str("bundle tag"),
+            Field::TimeTag(t) if t.is_immediately() => f.write_str("timetag immediately"),
+            Field::TimeTag(t) => write!(f, "timetag {}", t.to_bits()),
+            Field::ElementSize(size) => write!(f, "element size {size}"),
+            Field::Address(address) => write!(f, "address {address:?}"),
+            Field::TypeTags(tags) => write!(f, "type tags {tags:?}"),
+            Field::Argument { index, value } => {
+                write!(f, "argument {index} '{}': ", value.tag())?;
+                match *value {
+                    ArgView::Int(v) => write!(f, "{v}"),
+                    ArgView::Float(v) => write!(f, "{v:?}"),
+                    ArgView::String(s) | ArgView::Symbol(s) => write!(f, "{s:?}"),
+                    ArgView::Blob(b) => write!(f, "{} bytes", b.len()),
+                    ArgView::Long(v) => write!(f, "{v}"),
+                    ArgView::Time(t) => write!(f, "{}", t.to_bits()),
+                    ArgView::Double(v) => write!(f, "{v:?}"),
+                    ArgView::Char(c) => write!(f, "{c:?}"),
+                    ArgView::Color(c) => write!(
+                        f,
+                        "#{:02x}{:02x}{:02x}{:02x}",
+                        c.red, c.green, c.blue, c.alpha
+                    ),
+                    ArgView::Midi(m) => write!(
+                        f,
+                        "{:02x}:{:02x}:{:02x}:{:02x}",
+                        m.port, m.status, m.data1, m.data2
+                    ),
+                    ArgView::Bool(b) => write!(f, "{b}"),
+                    ArgView::Nil => f.write_str("nil"),
+                    ArgView::Inf => f.write_str("infinitum"),
+                    ArgView::Array(_) => f.write_str("array"),
+                }
+            }
+            Field::Padding => f.write_str("padding"),
+            Field::Payload(payload) => write!(f, "untyped payload, {} bytes", payload.len()),
+            Field::Ignored => f.write_str("ignored"),
+            Field::Invalid(err) => write!(f, "error: {err}"),
+        }
+    }
+}
+
+/// A range of bytes in an annotated packet and what it holds.
+#[derive(Debug, Clone)]
+pub struct Span<'a> {
+    /// The bytes of the packet this span covers.
+    ///
+    /// Only a [`Field::Invalid`] span can be empty, when the input ended
+    /// where more data was expected.
+    pub range: Range<usize>,
+    /// Number of bundle elements enclosing the span.
+    pub depth: usize,
+    /// What the bytes hold.
+    pub field: Field<'a>,
+}
+
+/// A packet split into annotated byte ranges, returned by [`annotate`].
+///
+/// The spans are in order and cover every byte of the packet exactly once.
+/// Displaying an `Annotation` renders a hexdump with one line per span (more
+/// for spans over 16 bytes): the offset, the bytes in hex and ASCII, and the
+/// field, indented by bundle depth.
+#[derive(Debug, Clone)]
+pub struct Annotation<'a> {
+    bytes: &'a [u8],
+    spans: Vec<Span<'a>>,
+}
+
+impl<'a> Annotation<'a> {
+    /// The annotated packet.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// The spans, in the order of the bytes they cover.
+    pub fn spans(&self) -> &[Span<'a>] {
+        &self.spans
+    }
+
+    /// The errors found, in the order of the bytes they cover.
+    ///
+    /// The first one is the error the `decode_*` functions report for the same
+    /// packet and options.
+    pub fn errors(&self) -> impl Iterator<Item = &Error> + '_ {
+        self.spans.iter().filter_map(|span| match &span.field {
+            Field::Invalid(err) => Some(err),
+            _ => None,
+        })
+    }
+
+    /// Whether the packet decoded without errors.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+}
+
+const HEXDUMP_WIDTH: usize = 16;
+
+impl fmt::Display for Annotation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for span in &self.spans {
+            let bytes = &self.bytes[span.range.clone()];
+            let mut lines = bytes.chunks(HEXDUMP_WIDTH);
+            // An empty span still gets a line to show its field.
+            let first = lines.next().unwrap_or(&[]);
+            write_hex_line(f, span.range.start, first)?;
+            writeln!(f, "  {:indent$}{}", "", span.field, indent = 2 * span.depth)?;
+            for (i, line) in lines.enumerate() {
+                write_hex_line(f, span.range.start + (i + 1) * HEXDUMP_WIDTH, line)?;
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write the offset, hex and ASCII columns of one hexdump line.
+fn write_hex_line(f: &mut fmt::Formatter<'_>, offset: usize, bytes: &[u8]) -> fmt::Result {
+    write!(f, "{offset:08x} ")?;
+    for i in 0..HEXDUMP_WIDTH {
+        match bytes.get(i) {
+            Some(b) => write!(f, " {b:02x}")?,
+            None => f.write_str("   ")?,
+        }
+    }
+    f.write_str("  |")?;
+    for &b in bytes {
+        let c = if b.is_ascii_graphic() || b == b' ' {
+            b as char
+        } else {
+            '.'
+        };
+        write!(f, "{c}")?;
+    }
+    write!(f, "{:1$}|", "", HEXDUMP_WIDTH - bytes.len())
+}
+
+/// Annotation state shared by one packet and everything nested in it.
+///
+/// Mirrors `Decoder`, but records a span for every field it reads and keeps
+/// going after errors wherever the size of the offending item is known.
+/// Spans are recorded relative to the buffer being annotated; the bundle code
+/// shifts those of each element once the element is done.
+struct Annotator<'a, 'o> {
+    options: &'o DecodeOptions,
+    limits: &'o Limits,
+    /// Bundle elements annotated so far, over all nesting levels.
+    elements: usize,
+    spans: Vec<Span<'a>>,
+}
+
+impl<'a, 'o> Annotator<'a, 'o> {
+    fn new(options: &'o DecodeOptions) -> Self {
+        Self {
+            options,
+            limits: options.limits(),
+            elements: 0,
+            spans: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, range: Range<usize>, field: Field<'a>) {
+        if !range.is_empty() || matches!(field, Field::Invalid(_)) {
+            self.spans.push(Span {
+                range,
+                depth: 0,
+                field,
+            });
+        }
+    }
+
+    /// Mark the bytes from `from` to the end of `bytes` as invalid and stop.
+    fn stop(&mut self, bytes: &[u8], from: usize, err: Error) -> Option<usize> {
+        self.push(from..bytes.len(), Field::Invalid(err));
+        None
+    }
+
+    fn has_errors(&self, mark: usize) -> bool {
+        self.spans[mark..]
+            .iter()
+            .any(|span| matches!(span.field, Field::Invalid(_)))
+    }
+
+    /// Push the padding span `range`, marking it invalid if it is not all
+    /// zeros in [`DecodeMode::Strict`].
+    fn padding(&mut self, bytes: &[u8], range: Range<usize>, arg: Option<(usize, char)>) {
+        let field = match bytes[range.clone()].iter().position(|&b| b != 0) {
+            Some(pos) if self.options.mode() == DecodeMode::Strict => {
+                let err = Error::new(ErrorKind::NonZeroPadding, range.start + pos);
+                Field::Invalid(with_arg(err, arg))
+            }
+            _ => Field::Padding,
+        };
+        self.push(range, field);
+    }
+
+    /// Annotate the string at `off` and its padding, returning the offset
+    /// after the padding.
+    ///
+    /// A string that is not valid UTF-8 or too long is marked invalid but
+    /// skipped, since its end is still known.
+    fn string(
+        &mut self,
+        bytes: &'a [u8],
+        off: usize,
+        arg: Option<(usize, char)>,
+        field: impl FnOnce(&'a str) -> Field<'a>,
+    ) -> Option<usize> {
+        let (field, end) = match get_cstr_4(bytes, off) {
+            Ok((s, end)) if s.len() > self.limits.max_string_size => (
+                Field::Invalid(with_arg(Error::new(ErrorKind::StringTooLarge, off), arg)),
+                end,
+            ),
+            Ok((s, end)) => (field(s), end),
+            Err(err) if err.kind() == ErrorKind::InvalidUtf8 => {
+                let len = bytes[off..].iter().position(|&b| b == 0).unwrap_or(0);
+                let end = off + len + 1 + pad4_len(len + 1);
+                if end > bytes.len() {
+                    return self.stop(bytes, off, with_arg(err, arg));
+                }
+                (Field::Invalid(with_arg(err, arg)), end)
+            }
+            Err(err) => return self.stop(bytes, off, with_arg(err, arg)),
+        };
+        let nul = off + bytes[off..end].iter().position(|&b| b == 0).unwrap_or(0);
+        self.push(off..nul, field);
+        self.padding(bytes, nul..end, arg);
+        Some(end)
+    }
+
+    /// Annotate a message, returning the number of bytes it used, or `None`
+    /// if decoding stopped before its end.
+    fn message(&mut self, bytes: &'a [u8]) -> Option<usize> {
+        let strict = self.options.mode() == DecodeMode::Strict;
+        let tag_start = self.string(bytes, 0, None, |address| {
+            match address.bytes().position(|b| !b.is_ascii()) {
+                Some(pos) if strict => Field::Invalid(Error::new(ErrorKind::NonAsciiAddress, pos)),
+                _ => Field::Address(address),
+            }
+        })?;
+        if self.options.mode() == DecodeMode::Lenient && bytes.get(tag_start) != Some(&b',') {
+            let payload = &bytes[tag_start..];
+            if payload.len() > self.limits.max_blob_size {
+                let err = Error::new(ErrorKind::BlobTooLarge, tag_start);
+                return self.stop(bytes, tag_start, err);
+            }
+            self.push(tag_start..bytes.len(), Field::Payload(payload));
+            return Some(bytes.len());
+        }
+        if tag_start == bytes.len() {
+            let err = Error::new(ErrorKind::MissingTypeTags, tag_start);
+            return self.stop(bytes, tag_start, err);
+        }
+        let (tag, mut off) = match get_cstr_4(bytes, tag_start) {
+            Ok(tag) => tag,
+            Err(err) => return self.stop(bytes, tag_start, err),
+        };
+        let Some(tags) = tag.strip_prefix(',') else {
+            let err = Error::new(ErrorKind::MissingTypeTags, tag_start);
+            return self.stop(bytes, tag_start, err);
+        };
+        self.push(tag_start..tag_start + tag.len(), Field::TypeTags(tag));
+        self.padding(bytes, tag_start + tag.len()..off, None);
+        if tags.len() > self.limits.max_args {
+            let err = Error::new(ErrorKind::TooManyArguments, tag_start);
+            return self.stop(bytes, off, err);
+        }
+
+        // The argument index and offset of each open `[`, innermost last.
+        let mut open = Vec::new();
+        for (index, (pos, t)) in tags.char_indices().enumerate() {
+            let tag_off = tag_start + 1 + pos;
+            match t {
+                '[' => {
+                    if open.len() >= self.limits.max_depth {
+                        let err = Error::new(ErrorKind::NestingTooDeep, tag_off);
+                        return self.stop(bytes, off, err.with_arg(index, t));
+                    }
+                    open.push((index, tag_off));
+                }
+                ']' => {
+                    if open.pop().is_none() {
+                        let err = Error::new(ErrorKind::UnbalancedArray, tag_off);
+                        return self.stop(bytes, off, err.with_arg(index, t));
+                    }
+                }
+                's' | 'S' => {
+                    off = self.string(bytes, off, Some((index, t)), |s| Field::Argument {
+                        index,
+                        value: if t == 's' {
+                            ArgView::String(s)
+                        } else {
+                            ArgView::Symbol(s)
+                        },
+                    })?;
+                }
+                _ => {
+                    let start = off;
+                    match view::get_arg(t, bytes, &mut off) {
+                        Ok(Some(ArgView::Blob(blob))) => {
+                            let end = start + 4 + blob.len();
+                            let field = if blob.len() > self.limits.max_blob_size {
+                                let err = Error::new(ErrorKind::BlobTooLarge, start);
+                                Field::Invalid(err.with_arg(index, t))
+                            } else {
+                                Field::Argument {
+                                    index,
+                                    value: ArgView::Blob(blob),
+                                }
+                            };
+                            self.push(start..end, field);
+                            self.padding(bytes, end..off, Some((index, t)));
+                        }
+                        Ok(Some(value)) => self.push(start..off, Field::Argument { index, value }),
+                        Ok(None) => {
+                            let err = Error::new(ErrorKind::UnknownTypeTag, tag_off);
+                            return self.stop(bytes, start, err.with_arg(index, t));
+                        }
+                        // The four bytes were read, they just do not form a char.
+                        Err(err) if err.kind() == ErrorKind::InvalidChar => {
+                            self.push(start..off, Field::Invalid(err.with_arg(index, t)));
+                        }
+                        Err(err) => return self.stop(bytes, start, err.with_arg(index, t)),
+                    }
+                }
+            }
+        }
+        if let Some(&(index, tag_off)) = open.last() {
+            let err = Error::new(ErrorKind::UnbalancedArray, tag_off);
+            return self.stop(bytes, off, err.with_arg(index, '['));
+        }
+        Some(off)
+    }
+
+    /// Annotate a bundle whose elements are at nesting depth `depth + 1`.
+    ///
+    /// `bytes` must start with the `#bundle` string.
+    fn bundle(&mut self, bytes: &'a [u8], depth: usize) {
+        let tag_end = BUNDLE_PREFIX.len();
+        self.push(0..tag_end, Field::BundleTag);
+        let mut off = tag_end + 8;
+        let Some(timetag) = bytes.get(tag_end..off) else {
+            let err = Error::new(ErrorKind::UnexpectedEof, tag_end);
+            self.stop(bytes, tag_end, err);
+            return;
+        };
+        let bits = u64::from_be_bytes(timetag.try_into().expect("8 bytes"));
+        self.push(tag_end..off, Field::TimeTag(TimeTag::from_bits(bits)));
+
+        let mut index = 0;
+        while off < bytes.len() {
+            let size_off = off;
+            if depth >= self.limits.max_depth {
+                let err = Error::new(ErrorKind::NestingTooDeep, size_off);
+                self.stop(bytes, size_off, err);
+                return;
+            }
+            if self.elements >= self.limits.max_elements {
+                let err = Error::new(ErrorKind::TooManyElements, size_off);
+                self.stop(bytes, size_off, err);
+                return;
+            }
+            self.elements += 1;
+
+            let size = match get_i32(bytes, &mut off) {
+                Ok(size) => size,
+                Err(err) => {
+                    self.stop(bytes, size_off, err);
+                    return;
+                }
+            };
+            self.push(size_off..off, Field::ElementSize(size));
+            let Some(size) = usize::try_from(size)
+                .ok()
+                .filter(|&size| size <= bytes.len() - off)
+            else {
+                let err = Error::new(ErrorKind::InvalidElementSize, size_off);
+                self.stop(bytes, off, err);
+                return;
+            };
+
+            let mark = self.spans.len();
+            let used = self.element(&bytes[off..off + size], depth);
+            for span in &mut self.spans[mark..] {
+                span.range = span.range.start + off..span.range.end + off;
+                span.depth += 1;
+                if let Field::Invalid(err) = &mut span.field {
+                    *err = err.clone().in_element(index, off);
+                }
+            }
+            if let Some(used) = used.filter(|&used| used != size) {
+                let field = if self.options.mode() == DecodeMode::Lenient {
+                    Field::Ignored
+                } else {
+                    Field::Invalid(Error::new(ErrorKind::InvalidElementSize, size_off))
+                };
+                self.push(off + used..off + size, field);
+            }
+
+            index += 1;
+            off += size;
+        }
+    }
+
+    /// Annotate the element of a bundle at depth `depth`, returning the number
+    /// of bytes it used, or `None` if decoding stopped before its end.
+    fn element(&mut self, bytes: &'a [u8], depth: usize) -> Option<usize> {
+        // An element is a bundle if and only if it starts with the `#bundle` string.
+        if !bytes.starts_with(BUNDLE_PREFIX) {
+            return self.message(bytes);
+        }
+        let mark = self.spans.len();
+        let elements = self.elements;
+        self.bundle(bytes, depth + 1);
+        if self.options.bundle_fallback() && self.has_errors(mark) {
+            // Legacy senders may address messages to "#bundle".
+            let bundle = self.spans.split_off(mark);
+            let bundle_elements = core::mem::replace(&mut self.elements, elements);
+            if self.message(bytes) == Some(bytes.len()) && !self.has_errors(mark) {
+                return Some(bytes.len());
+            }
+            // Neither reading fits; show why it is not a valid bundle.
+            self.spans.truncate(mark);
+            self.spans.extend(bundle);
+            self.elements = bundle_elements;
+        }
+        Some(bytes.len())
+    }
+
+    fn packet(&mut self, bytes: &'a [u8]) {
+        if bytes.starts_with(BUNDLE_PREFIX) {
+            self.bundle(bytes, 0);
+        } else if let Some(used) = self.message(bytes).filter(|&used| used != bytes.len()) {
+            let field = if self.options.mode() == DecodeMode::Lenient {
+                Field::Ignored
+            } else {
+                Field::Invalid(Error::new(ErrorKind::TrailingBytes, used))
+            };
+            self.push(used..bytes.len(), field);
+        }
+    }
+}
+
+/// Attach the argument `arg`, if any, to `err`.
+fn with_arg(err: Error, arg: Option<(usize, char)>) -> Error {
+    match arg {
+        Some((index, tag)) => err.with_arg(index, tag),
+        None => err,
+    }
+}
+
+/// Split a packet into annotated byte ranges: addresses, type tags, padding,
+/// arguments, bundle element sizes and time tags.
+///
+/// Meant for debugging packets the decoders reject. Instead of stopping at
+/// the first problem, annotation marks the offending bytes as
+/// [`Field::Invalid`] and continues wherever the size of the offending item
+/// is known: after a string that is not valid UTF-8, a `c` argument that is
+/// not a char, or a bundle element that does not decode. Otherwise the rest of
+/// the message or bundle is marked invalid.
+///
+/// ```
+/// use osc_codec10::{annotate, encode_message, ErrorKind};
+/// use osc_types10::{Message, OscType};
+///
+/// let mut bytes = encode_message(&Message::new("/a", vec![OscType::Int(1), OscType::Int(2)]));
+/// bytes[6] = b'x'; // ",ix": 'x' is not a known type tag.
+///
+/// let annotation = annotate(&bytes);
+/// assert_eq!(annotation.errors().next().unwrap().kind(), ErrorKind::UnknownTypeTag);
+/// assert_eq!(
+///     annotation.to_string(),
+///     "\
+/// 00000000  2f 61                                            |/a              |  address \"/a\"
+/// 00000002  00 00                                            |..              |  padding
+/// 00000004  2c 69 78                                         |,ix             |  type tags \",ix\"
+/// 00000007  00                                               |.               |  padding
+/// 00000008  00 00 00 01                                      |....            |  argument 0 'i': 1
+/// 0000000c  00 00 00 02                                      |....            |  error: unknown type tag at byte 6 (argument 1, type tag 'x')
+/// "
+/// );
+/// ```
+pub fn annotate(bytes: &[u8]) -> Annotation<'_> {
+    annotate_with(bytes, &DecodeOptions::default())
+}
+
+/// Like [`annotate`], enforcing the limits and mode in `options` like
+/// [`decode_packet_with`](crate::decode_packet_with).
+pub fn annotate_with<'a>(bytes: &'a [u8], options: &DecodeOptions) -> Annotation<'a> {
+    let mut annotator = Annotator::new(options);
+    annotator.packet(bytes);
+    Annotation {
+        bytes,
+        spans: annotator.spans,
+    }
+}
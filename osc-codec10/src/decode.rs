@@ -0,0 +1,283 @@
+//! Decoding messages, bundles and packets.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use osc_types10::{Bundle, Message, OscPacket, OscType, TimeTag};
+
+use crate::view::{self, ArgView};
+use crate::{
+    get_cstr_4, get_i32, get_u64, DecodeMode, DecodeOptions, Error, ErrorKind, Limits, Result,
+    BUNDLE_PREFIX, BUNDLE_TAG,
+};
+
+/// Decoding state shared by one packet and everything nested in it.
+struct Decoder<'o> {
+    options: &'o DecodeOptions,
+    limits: &'o Limits,
+    /// Bundle elements decoded so far, over all nesting levels.
+    elements: usize,
+}
+
+impl<'o> Decoder<'o> {
+    fn new(options: &'o DecodeOptions) -> Self {
+        Self {
+            options,
+            limits: options.limits(),
+            elements: 0,
+        }
+    }
+
+    /// Read an address or string and check it against the size limit.
+    fn string<'a>(&self, bytes: &'a [u8], off: usize) -> Result<(&'a str, usize)> {
+        let (s, next) = get_cstr_4(bytes, off)?;
+        if s.len() > self.limits.max_string_size {
+            return Err(Error::new(ErrorKind::StringTooLarge, off));
+        }
+        Ok((s, next))
+    }
+
+    /// In [`DecodeMode::Strict`], check that the padding `bytes[from..to]` is all zeros.
+    fn padding(&self, bytes: &[u8], from: usize, to: usize) -> Result<()> {
+        if self.options.mode() != DecodeMode::Strict {
+            return Ok(());
+        }
+        match bytes[from..to].iter().position(|&b| b != 0) {
+            Some(pos) => Err(Error::new(ErrorKind::NonZeroPadding, from + pos)),
+            None => Ok(()),
+        }
+    }
+
+    /// Decode the data of a single non-array argument with type tag `t` at `*off`.
+    ///
+    /// Returns `Ok(None)` if `t` is not a known type tag.
+    fn arg<'a>(&self, t: char, bytes: &'a [u8], off: &mut usize) -> Result<Option<OscType<'a>>> {
+        let start = *off;
+        let Some(arg) = view::get_arg(t, bytes, off)? else {
+            return Ok(None);
+        };
+        let too_large = match arg {
+            ArgView::String(s) | ArgView::Symbol(s) => {
+                (s.len() > self.limits.max_string_size).then_some(ErrorKind::StringTooLarge)
+            }
+            ArgView::Blob(b) => {
+                (b.len() > self.limits.max_blob_size).then_some(ErrorKind::BlobTooLarge)
+            }
+            _ => None,
+        };
+        if let Some(kind) = too_large {
+            return Err(Error::new(kind, start));
+        }
+        match arg {
+            ArgView::String(s) | ArgView::Symbol(s) => {
+                self.padding(bytes, start + s.len() + 1, *off)?
+            }
+            ArgView::Blob(b) => self.padding(bytes, start + 4 + b.len(), *off)?,
+            _ => {}
+        }
+        arg.to_osc_type().map(Some)
+    }
+
+    fn message<'a>(&mut self, bytes: &'a [u8]) -> Result<(Message<'a>, usize)> {
+        let (address, tag_start) = self.string(bytes, 0)?;
+        if self.options.mode() == DecodeMode::Strict {
+            if let Some(pos) = address.bytes().position(|b| !b.is_ascii()) {
+                return Err(Error::new(ErrorKind::NonAsciiAddress, pos));
+            }
+        }
+        self.padding(bytes, address.len() + 1, tag_start)?;
+        if self.options.mode() == DecodeMode::Lenient && bytes.get(tag_start) != Some(&b',') {
+            // Legacy senders may omit the type tag string; hand over the raw payload.
+            let payload = &bytes[tag_start..];
+            if payload.len() > self.limits.max_blob_size {
+                return Err(Error::new(ErrorKind::BlobTooLarge, tag_start));
+            }
+            let mut args = Vec::new();
+            if !payload.is_empty() {
+                args.push(OscType::Blob(payload));
+            }
+            return Ok((Message::new(address, args), bytes.len()));
+        }
+        if tag_start == bytes.len() {
+            return Err(Error::new(ErrorKind::MissingTypeTags, tag_start));
+        }
+        let (tag, mut off) = get_cstr_4(bytes, tag_start)?;
+        let Some(tags) = tag.strip_prefix(',') else {
+            return Err(Error::new(ErrorKind::MissingTypeTags, tag_start));
+        };
+        self.padding(bytes, tag_start + tag.len() + 1, off)?;
+        if tags.len() > self.limits.max_args {
+            return Err(Error::new(ErrorKind::TooManyArguments, tag_start));
+        }
+
+        // For each currently open array, innermost last: the argument index and
+        // offset of its `[`, and the arguments collected outside it so far.
+        let mut open: Vec<(usize, usize, Vec<OscType<'a>>)> = Vec::new();
+        let mut args = Vec::new();
+        for (index, (pos, t)) in tags.char_indices().enumerate() {
+            let tag_off = tag_start + 1 + pos;
+            let arg = match t {
+                '[' => {
+                    if open.len() >= self.limits.max_depth {
+                        return Err(
+                            Error::new(ErrorKind::NestingTooDeep, tag_off).with_arg(index, t)
+                        );
+                    }
+                    open.push((index, tag_off, core::mem::take(&mut args)));
+                    continue;
+                }
+                ']' => {
+                    let Some((_, _, outer)) = open.pop() else {
+                        return Err(
+                            Error::new(ErrorKind::UnbalancedArray, tag_off).with_arg(index, t)
+                        );
+                    };
+                    OscType::Array(core::mem::replace(&mut args, outer))
+                }
+                _ => match self
+                    .arg(t, bytes, &mut off)
+                    .map_err(|e| e.with_arg(index, t))?
+                {
+                    Some(arg) => arg,
+                    None => {
+                        return Err(
+                            Error::new(ErrorKind::UnknownTypeTag, tag_off).with_arg(index, t)
+                        )
+                    }
+                },
+            };
+            args.push(arg);
+        }
+        if let Some(&(index, tag_off, _)) = open.last() {
+            return Err(Error::new(ErrorKind::UnbalancedArray, tag_off).with_arg(index, '['));
+        }
+
+        Ok((Message::new(address, args), off))
+    }
+
+    /// Decode a bundle whose elements are at nesting depth `depth + 1`.
+    fn bundle<'a>(&mut self, bytes: &'a [u8], depth: usize) -> Result<(Bundle<'a>, usize)> {
+        let (tag, mut off) = get_cstr_4(bytes, 0)?;
+        if tag != BUNDLE_TAG {
+            return Err(Error::new(ErrorKind::InvalidBundleTag, 0));
+        }
+        let timetag = TimeTag::from_bits(get_u64(bytes, &mut off)?);
+
+        let mut packets = Vec::new();
+        while off < bytes.len() {
+            let size_off = off;
+            if depth >= self.limits.max_depth {
+                return Err(Error::new(ErrorKind::NestingTooDeep, size_off));
+            }
+            if self.elements >= self.limits.max_elements {
+                return Err(Error::new(ErrorKind::TooManyElements, size_off));
+            }
+            self.elements += 1;
+
+            let size = get_i32(bytes, &mut off)?;
+            let size = usize::try_from(size)
+                .ok()
+                .filter(|&size| size <= bytes.len() - off)
+                .ok_or(Error::new(ErrorKind::InvalidElementSize, size_off))?;
+
+            let index = packets.len();
+            let element_bytes = &bytes[off..off + size];
+            let in_element = |e: Error| e.in_element(index, off);
+
+            // An element is a bundle if and only if it starts with the `#bundle` string.
+            if element_bytes.starts_with(BUNDLE_PREFIX) {
+                let elements = self.elements;
+                match self.bundle(element_bytes, depth + 1) {
+                    Ok((bundle, _)) => packets.push(OscPacket::Bundle(bundle)),
+                    Err(bundle_err) if self.options.bundle_fallback() => {
+                        // Legacy senders may address messages to "#bundle".
+                        self.elements = elements;
+                        match self.message(element_bytes) {
+                            Ok((msg, used)) if used == size => {
+                                packets.push(OscPacket::Message(msg))
+                            }
+                            // Neither reading fits; report why it is not a valid bundle.
+                            _ => return Err(in_element(bundle_err)),
+                        }
+                    }
+                    Err(bundle_err) => return Err(in_element(bundle_err)),
+                }
+            } else {
+                let (msg, used) = self.message(element_bytes).map_err(in_element)?;
+                if used != size && self.options.mode() != DecodeMode::Lenient {
+                    return Err(Error::new(ErrorKind::InvalidElementSize, size_off));
+                }
+                packets.push(OscPacket::Message(msg));
+            }
+
+            off += size;
+        }
+        Ok((Bundle::new(timetag, packets), off))
+    }
+
+    fn packet<'a>(&mut self, bytes: &'a [u8]) -> Result<OscPacket<'a>> {
+        if bytes.starts_with(BUNDLE_PREFIX) {
+            let (bundle, _) = self.bundle(bytes, 0)?;
+            Ok(OscPacket::Bundle(bundle))
+        } else {
+            let (msg, used) = self.message(bytes)?;
+            if used != bytes.len() && self.options.mode() != DecodeMode::Lenient {
+                return Err(Error::new(ErrorKind::TrailingBytes, used));
+            }
+            Ok(OscPacket::Message(msg))
+        }
+    }
+}
+
+/// Decode a single OSC message from bytes, returning the message and number of bytes consumed.
+pub fn decode_message<'a>(bytes: &'a [u8]) -> Result<(Message<'a>, usize)> {
+    decode_message_with(bytes, &DecodeOptions::default())
+}
+
+/// Like [`decode_message`], enforcing the limits and mode in `options`.
+///
+/// In [`DecodeMode::Strict`] every byte must be consumed, as in [`decode_packet`].
+pub fn decode_message_with<'a>(
+    bytes: &'a [u8],
+    options: &DecodeOptions,
+) -> Result<(Message<'a>, usize)> {
+    let (msg, used) = Decoder::new(options).message(bytes)?;
+    if used != bytes.len() && options.mode() == DecodeMode::Strict {
+        return Err(Error::new(ErrorKind::TrailingBytes, used));
+    }
+    Ok((msg, used))
+}
+
+/// Decode a bundle that can contain messages and nested bundles. Returns the bundle and number of bytes consumed.
+///
+/// An element is decoded as a bundle if and only if it starts with the `#bundle`
+/// string; see [`DecodeOptions::with_bundle_fallback`] for legacy senders.
+pub fn decode_bundle<'a>(bytes: &'a [u8]) -> Result<(Bundle<'a>, usize)> {
+    decode_bundle_with(bytes, &DecodeOptions::default())
+}
+
+/// Like [`decode_bundle`], enforcing the limits in `options`.
+pub fn decode_bundle_with<'a>(
+    bytes: &'a [u8],
+    options: &DecodeOptions,
+) -> Result<(Bundle<'a>, usize)> {
+    Decoder::new(options).bundle(bytes, 0)
+}
+
+/// Decode a complete packet, detecting whether it is a message or a bundle.
+///
+/// Following the OSC 1.0 spec, a packet is a bundle if and only if it starts with
+/// the `#bundle` string (`"#bundle\0"`); anything else is decoded as a message.
+/// Every byte must be consumed: leftover bytes after a message yield
+/// [`ErrorKind::TrailingBytes`].
+pub fn decode_packet(bytes: &[u8]) -> Result<OscPacket<'_>> {
+    decode_packet_with(bytes, &DecodeOptions::default())
+}
+
+/// Like [`decode_packet`], enforcing the limits in `options`.
+pub fn decode_packet_with<'a>(bytes: &'a [u8], options: &DecodeOptions) -> Result<OscPacket<'a>> {
+    Decoder::new(options).packet(bytes)
+}
@@ -0,0 +1,242 @@
+//! Encoding messages, bundles and packets.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use osc_types10::{
+    AsOscType, Bundle, BundleBuf, Message, MessageBuf, OscPacket, OscPacketBuf, OscType, TimeTag,
+};
+
+use crate::{
+    pad4_len, put_f32, put_f64, put_i32, put_i64, put_nul_padding, put_str, put_u32, put_u64,
+    Counter, Result, Sink, SliceSink, BUNDLE_PREFIX, ZEROS,
+};
+
+impl Sink for Vec<u8> {
+    fn put(&mut self, bytes: &[u8]) -> Result<()> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn position(&self) -> usize {
+        self.len()
+    }
+
+    fn patch(&mut self, at: usize, bytes: [u8; 4]) {
+        self[at..at + 4].copy_from_slice(&bytes);
+    }
+}
+
+/// A message, bundle or packet the encoders accept, borrowed or owned.
+///
+/// Implemented for [`OscPacket`], [`Message`] and [`Bundle`] and their owned
+/// counterparts [`OscPacketBuf`], [`MessageBuf`] and [`BundleBuf`], which are
+/// encoded directly without borrowing them first. This trait is sealed.
+pub trait Encode: sealed::Packet {}
+
+mod sealed {
+    use crate::{Result, Sink};
+
+    /// Writes a packet; kept private so the sink stays an implementation detail.
+    pub trait Packet {
+        fn put(&self, buf: &mut impl Sink) -> Result<()>;
+    }
+}
+
+impl<T: sealed::Packet> Encode for T {}
+
+impl sealed::Packet for OscPacket<'_> {
+    fn put(&self, buf: &mut impl Sink) -> Result<()> {
+        match self {
+            OscPacket::Message(msg) => msg.put(buf),
+            OscPacket::Bundle(b) => b.put(buf),
+        }
+    }
+}
+
+impl sealed::Packet for OscPacketBuf {
+    fn put(&self, buf: &mut impl Sink) -> Result<()> {
+        match self {
+            OscPacketBuf::Message(msg) => msg.put(buf),
+            OscPacketBuf::Bundle(b) => b.put(buf),
+        }
+    }
+}
+
+impl sealed::Packet for Message<'_> {
+    fn put(&self, buf: &mut impl Sink) -> Result<()> {
+        put_message(buf, self.address, &self.args)
+    }
+}
+
+impl sealed::Packet for MessageBuf {
+    fn put(&self, buf: &mut impl Sink) -> Result<()> {
+        put_message(buf, &self.address, &self.args)
+    }
+}
+
+impl sealed::Packet for Bundle<'_> {
+    fn put(&self, buf: &mut impl Sink) -> Result<()> {
+        put_bundle(buf, self.timetag, &self.packets)
+    }
+}
+
+impl sealed::Packet for BundleBuf {
+    fn put(&self, buf: &mut impl Sink) -> Result<()> {
+        put_bundle(buf, self.timetag, &self.packets)
+    }
+}
+
+/// Number of type tag characters `args` need, array brackets included.
+fn tag_count(args: &[impl AsOscType]) -> usize {
+    args.iter()
+        .map(|a| match a.items() {
+            Some(items) => 2 + tag_count(items),
+            None => 1,
+        })
+        .sum()
+}
+
+fn put_tags(buf: &mut impl Sink, args: &[impl AsOscType]) -> Result<()> {
+    for a in args {
+        // Every type tag is ASCII.
+        buf.put(&[a.tag() as u8])?;
+        if let Some(items) = a.items() {
+            put_tags(buf, items)?;
+            buf.put(b"]")?;
+        }
+    }
+    Ok(())
+}
+
+fn put_arg(buf: &mut impl Sink, a: &impl AsOscType) -> Result<()> {
+    if let Some(items) = a.items() {
+        return items.iter().try_for_each(|item| put_arg(buf, item));
+    }
+    match a.as_osc_type() {
+        OscType::Int(v) => put_i32(buf, v),
+        OscType::Float(v) => put_f32(buf, v),
+        OscType::String(s) | OscType::Symbol(s) => put_str(buf, s),
+        OscType::Blob(b) => {
+            put_i32(buf, b.len() as i32)?;
+            buf.put(b)?;
+            buf.put(&ZEROS[..pad4_len(b.len())])
+        }
+        OscType::Long(v) => put_i64(buf, v),
+        OscType::Time(t) => put_u64(buf, t.to_bits()),
+        OscType::Double(v) => put_f64(buf, v),
+        OscType::Char(c) => put_u32(buf, c as u32),
+        OscType::Color(c) => buf.put(&[c.red, c.green, c.blue, c.alpha]),
+        OscType::Midi(m) => buf.put(&[m.port, m.status, m.data1, m.data2]),
+        // Carried by the type tag alone; arrays are handled above.
+        OscType::Bool(_) | OscType::Nil | OscType::Inf | OscType::Array(_) => Ok(()),
+    }
+}
+
+fn put_message(buf: &mut impl Sink, address: &str, args: &[impl AsOscType]) -> Result<()> {
+    put_str(buf, address)?;
+
+    // Type tag (starts with ',')
+    buf.put(b",")?;
+    put_tags(buf, args)?;
+    put_nul_padding(buf, 1 + tag_count(args))?;
+
+    args.iter().try_for_each(|a| put_arg(buf, a))
+}
+
+fn put_bundle(buf: &mut impl Sink, timetag: TimeTag, packets: &[impl Encode]) -> Result<()> {
+    buf.put(BUNDLE_PREFIX)?;
+    // 64-bit big-endian NTP timetag
+    put_u64(buf, timetag.to_bits())?;
+
+    // Elements are written in place behind a placeholder size, which is
+    // filled in once the element is complete.
+    for packet in packets {
+        let size_at = buf.position();
+        buf.put(&ZEROS)?;
+        packet.put(buf)?;
+        let size = buf.position() - size_at - 4;
+        buf.patch(size_at, (size as i32).to_be_bytes());
+    }
+    Ok(())
+}
+
+/// Encode into a fresh `Vec`.
+fn encode_to_vec(packet: &impl Encode) -> Vec<u8> {
+    let mut buf = Vec::new();
+    packet.put(&mut buf).expect("writing to a Vec cannot fail");
+    buf
+}
+
+/// Encode a single OSC message into bytes.
+pub fn encode_message(msg: &Message<'_>) -> Vec<u8> {
+    encode_to_vec(msg)
+}
+
+/// Encode a bundle that can contain messages and nested bundles.
+///
+/// Elements are written straight into the output, and their size prefixes are
+/// filled in afterwards, so nesting costs no extra allocation or copy.
+pub fn encode_bundle(b: &Bundle<'_>) -> Vec<u8> {
+    encode_to_vec(b)
+}
+
+/// Encode a message or bundle, borrowed or owned, into bytes.
+///
+/// ```
+/// use osc_codec10::{encode_message, encode_packet};
+/// use osc_types10::{osc, MessageBuf};
+///
+/// let msg = osc!("/sensor", 0.5f32);
+/// let owned = MessageBuf::from(&msg);
+/// assert_eq!(encode_packet(&owned), encode_message(&msg));
+/// ```
+pub fn encode_packet(packet: &impl Encode) -> Vec<u8> {
+    encode_to_vec(packet)
+}
+
+/// Number of bytes [`encode_packet`] produces for `packet`, computed without
+/// allocating.
+pub fn encoded_len(packet: &impl Encode) -> usize {
+    let mut len = Counter(0);
+    let _ = packet.put(&mut len);
+    len.0
+}
+
+/// Encode `packet` into the start of `buf` without allocating, returning the
+/// number of bytes written.
+///
+/// Fails with [`ErrorKind::BufferTooSmall`](crate::ErrorKind::BufferTooSmall) if `buf` is shorter than
+/// [`encoded_len`]; the contents of `buf` are then unspecified.
+///
+/// ```
+/// use osc_codec10::{encode_into, encoded_len, ErrorKind};
+/// use osc_types10::{Message, OscPacket, OscType};
+///
+/// let packet = OscPacket::Message(Message::new("/sensor", vec![OscType::Float(0.5)]));
+/// let mut buf = [0u8; 64];
+/// let n = encode_into(&packet, &mut buf).unwrap();
+/// assert_eq!(n, encoded_len(&packet));
+///
+/// let err = encode_into(&packet, &mut buf[..8]).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::BufferTooSmall);
+/// ```
+pub fn encode_into(packet: &impl Encode, buf: &mut [u8]) -> Result<usize> {
+    let mut sink = SliceSink { buf, len: 0 };
+    packet.put(&mut sink)?;
+    Ok(sink.len)
+}
+
+/// Append the encoding of `packet` to `buf`, returning the number of bytes
+/// written.
+///
+/// Reusing one `Vec` across sends avoids allocating once its capacity has
+/// grown to fit the largest packet.
+pub fn encode_into_vec(packet: &impl Encode, buf: &mut Vec<u8>) -> usize {
+    let start = buf.len();
+    packet.put(buf).expect("writing to a Vec cannot fail");
+    buf.len() - start
+}
@@ -0,0 +1,214 @@
+//! Decoding errors with the position of the problem.
+
+use core::fmt;
+
+/// What went wrong while decoding or encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input ended before the current item was complete.
+    UnexpectedEof,
+    /// A string has no NUL terminator before the end of the input.
+    UnterminatedString,
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// The type tag string does not start with `,`.
+    MissingTypeTags,
+    /// The type tag string contains a tag this codec does not know.
+    UnknownTypeTag,
+    /// A `c` argument did not hold a valid Unicode scalar value.
+    InvalidChar,
+    /// The type tag string has a `]` without a matching `[`, or an unclosed `[`.
+    UnbalancedArray,
+    /// A bundle does not start with the `#bundle` string.
+    InvalidBundleTag,
+    /// A bundle element size is negative, exceeds the bundle, or does not match
+    /// the size of the element it prefixes.
+    InvalidElementSize,
+    /// Bytes were left over after a complete packet was decoded.
+    TrailingBytes,
+    /// A blob size is negative or exceeds the input.
+    InvalidBlobSize,
+    /// Bundles or arrays are nested deeper than [`Limits::max_depth`](crate::Limits::max_depth).
+    NestingTooDeep,
+    /// A message has more type tags than [`Limits::max_args`](crate::Limits::max_args).
+    TooManyArguments,
+    /// A blob is larger than [`Limits::max_blob_size`](crate::Limits::max_blob_size).
+    BlobTooLarge,
+    /// An address or string is longer than
+    /// [`Limits::max_string_size`](crate::Limits::max_string_size).
+    StringTooLarge,
+    /// A packet has more bundle elements than
+    /// [`Limits::max_elements`](crate::Limits::max_elements).
+    TooManyElements,
+    /// A padding byte is not zero, in [`DecodeMode::Strict`](crate::DecodeMode::Strict).
+    NonZeroPadding,
+    /// An address contains non-ASCII characters, in
+    /// [`DecodeMode::Strict`](crate::DecodeMode::Strict).
+    NonAsciiAddress,
+    /// The output buffer passed to [`encode_into`](crate::encode_into) is too
+    /// small; the offset is where the first byte that did not fit belongs.
+    BufferTooSmall,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ErrorKind::UnexpectedEof => "unexpected end of input",
+            ErrorKind::UnterminatedString => "string is not NUL-terminated",
+            ErrorKind::InvalidUtf8 => "string is not valid UTF-8",
+            ErrorKind::MissingTypeTags => "type tag string must start with ','",
+            ErrorKind::UnknownTypeTag => "unknown type tag",
+            ErrorKind::InvalidChar => "invalid character argument",
+            ErrorKind::UnbalancedArray => "unbalanced array brackets",
+            ErrorKind::InvalidBundleTag => "bundle must start with \"#bundle\"",
+            ErrorKind::InvalidElementSize => "invalid bundle element size",
+            ErrorKind::TrailingBytes => "trailing bytes after packet",
+            ErrorKind::InvalidBlobSize => "invalid blob size",
+            ErrorKind::NestingTooDeep => "nesting depth limit exceeded",
+            ErrorKind::TooManyArguments => "argument count limit exceeded",
+            ErrorKind::BlobTooLarge => "blob size limit exceeded",
+            ErrorKind::StringTooLarge => "string size limit exceeded",
+            ErrorKind::TooManyElements => "bundle element limit exceeded",
+            ErrorKind::NonZeroPadding => "padding byte is not zero",
+            ErrorKind::NonAsciiAddress => "address is not ASCII",
+            ErrorKind::BufferTooSmall => "output buffer too small",
+        })
+    }
+}
+
+const MAX_PATH_DEPTH: usize = 8;
+
+/// Error returned when decoding or encoding fails.
+///
+/// Besides the [`ErrorKind`] it records where decoding stopped: the byte
+/// offset in the input, the argument being decoded (if any) and the indices of
+/// the bundle elements that enclose it.
+///
+/// ```
+/// use osc_codec10::{ErrorKind, MessageView};
+///
+/// // "/a" with type tags ",ix" - 'x' is not a known type tag.
+/// let bytes = b"/a\0\0,ix\0\0\0\0\x01";
+/// let err = MessageView::new(bytes).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::UnknownTypeTag);
+/// assert_eq!(err.arg_index(), Some(1));
+/// assert_eq!(err.type_tag(), Some('x'));
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(err.to_string(), "unknown type tag at byte 6 (argument 1, type tag 'x')");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    offset: usize,
+    arg: Option<(usize, char)>,
+    path: [usize; MAX_PATH_DEPTH],
+    depth: usize,
+}
+
+impl Error {
+    /// Maximum number of bundle levels recorded in [`Error::path`].
+    pub const MAX_PATH_DEPTH: usize = MAX_PATH_DEPTH;
+
+    pub(crate) fn new(kind: ErrorKind, offset: usize) -> Self {
+        Self {
+            kind,
+            offset,
+            arg: None,
+            path: [0; MAX_PATH_DEPTH],
+            depth: 0,
+        }
+    }
+
+    /// Attach the argument being decoded, unless one is already recorded.
+    pub(crate) fn with_arg(mut self, index: usize, tag: char) -> Self {
+        self.arg.get_or_insert((index, tag));
+        self
+    }
+
+    /// Record that the error happened inside element `index` of a bundle whose
+    /// element data starts at `base` in the enclosing buffer.
+    pub(crate) fn in_element(mut self, index: usize, base: usize) -> Self {
+        self.offset += base;
+        // Keep the outermost levels if the nesting is deeper than we can record.
+        let kept = self.depth.min(MAX_PATH_DEPTH - 1);
+        self.path.copy_within(0..kept, 1);
+        self.path[0] = index;
+        self.depth += 1;
+        self
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Byte offset in the input where the offending item starts.
+    ///
+    /// For [`ErrorKind::UnknownTypeTag`] and [`ErrorKind::UnbalancedArray`] this
+    /// is the offending character in the type tag string; for other argument
+    /// errors it is the start of the argument data.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Index of the argument being decoded, counted over the type tags after
+    /// the leading `,` (array brackets included).
+    pub fn arg_index(&self) -> Option<usize> {
+        self.arg.map(|(index, _)| index)
+    }
+
+    /// Type tag of the argument being decoded.
+    pub fn type_tag(&self) -> Option<char> {
+        self.arg.map(|(_, tag)| tag)
+    }
+
+    /// Indices of the bundle elements enclosing the error, outermost first.
+    ///
+    /// Empty if the error is not inside a bundle element. Only the outermost
+    /// [`Error::MAX_PATH_DEPTH`] levels are recorded; see [`Error::depth`].
+    pub fn path(&self) -> &[usize] {
+        &self.path[..self.depth.min(MAX_PATH_DEPTH)]
+    }
+
+    /// Number of bundle elements enclosing the error.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Error")
+            .field("kind", &self.kind)
+            .field("offset", &self.offset)
+            .field("arg", &self.arg)
+            .field("path", &self.path())
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)?;
+        if let Some((index, tag)) = self.arg {
+            write!(f, " (argument {index}, type tag {tag:?})")?;
+        }
+        if self.depth > 0 {
+            f.write_str(" in bundle element ")?;
+            for (i, index) in self.path().iter().enumerate() {
+                if i > 0 {
+                    f.write_str("/")?;
+                }
+                write!(f, "{index}")?;
+            }
+            if self.depth > MAX_PATH_DEPTH {
+                f.write_str("/...")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
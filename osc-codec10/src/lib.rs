@@ -3,7 +3,14 @@
 //! - Zero-copy leaning: decoded Strings/Blobs borrow from the input buffer.
 //! - Strict 4-byte OSC alignment for strings/blobs.
 //! - Big endian numeric encoding per the OSC 1.0 spec.
-//! - Minimal scope: Messages and Bundles (bundle contains only messages in this first cut).
+//! - Messages and Bundles (bundles may nest); [`decode_packet`] tells them apart.
+//! - Decoding enforces configurable [`Limits`] on untrusted input.
+//! - [`DecodeMode`] trades spec strictness for legacy compatibility.
+//! - [`encode_into`] writes into a caller-provided buffer without allocating.
+//! - [`MessageView`] and [`BundleView`] decode lazily without allocating.
+//! - [`annotate`] explains a packet field by field, down to where it goes wrong.
+//! - With the `serde` feature, [`to_args`] and [`from_args`] map any
+//!   `Serialize`/`Deserialize` type to and from message arguments.
 //!
 //! ## no_std
 //! Default builds use `std`. For `no_std + alloc`:
@@ -11,29 +18,96 @@
 //! cargo build -p osc-codec10 --no-default-features --features alloc
 //! ```
 //!
+//! Without `alloc` only the allocation-free API is available: the views
+//! ([`MessageView`], [`BundleView`], [`PacketView`]), [`encode_message_into`]
+//! and [`encoded_message_len`] for messages, [`BundleWriter`] for bundles,
+//! and [`Error`]/[`ErrorKind`].
+//!
+//! ## serde
+//! The `serde` feature adds a data format that sends any `Serialize` type as
+//! message arguments ([`to_args`], [`to_message`], [`to_vec`]) and reads it
+//! back ([`from_args`], [`from_message`], [`from_slice`]).
+//!
+//! Structs, tuples and tuple structs are flattened: each field becomes the
+//! next argument of the surrounding list, so
+//! `struct Note { pitch: i32, velocity: f32 }` is sent as `,if`. Scalars map
+//! to the argument with the same width:
+//!
+//! | Rust | OSC |
+//! |------|-----|
+//! | `bool` | `T` / `F` |
+//! | `i8`, `i16`, `i32`, `u8`, `u16` | `i` |
+//! | `i64`, `u32`, `u64` (up to `i64::MAX`) | `h` |
+//! | `f32` / `f64` | `f` / `d` |
+//! | `char` | `c` |
+//! | `osc_types10::TimeTag` | `t` |
+//! | `&str`, `String` | `s` |
+//! | bytes (`serde_bytes::Bytes`, `ByteBuf`, ...) | `b` |
+//! | `None`, `()` and unit structs | `N` |
+//! | `Some(v)`, newtype structs | `v` |
+//! | sequences (`Vec<T>`, `[T; N]`, ...) | `[...]` |
+//! | maps | `[key value key value ...]` |
+//! | enum variants | `s` with the variant name, then the variant's fields |
+//!
+//! `Vec<u8>` is a sequence to serde and becomes an array of `i`; wrap it in
+//! `serde_bytes::ByteBuf` or similar to send a blob.
+//!
+//! Deserializing reads the same layout back. Integers accept `i` and `h` and
+//! fail with [`ArgsErrorKind::OutOfRange`] if the value does not fit, `f64`
+//! accepts `f` and `d`, strings accept `s` and `S`, and missing trailing
+//! arguments deserialize as `None`. Strings and blobs are borrowed from the
+//! arguments, so `&str` and `&serde_bytes::Bytes` fields work.
+//!
 //! ## Examples
 //! See `examples/` for UDP send/recv samples (require `std`).
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
-use alloc::string::String;
-use alloc::vec::Vec;
+
 use byteorder::{BigEndian, ByteOrder};
-use core::str;
-use osc_types10::{Bundle, Message, OscPacket, OscType};
 
-/// Errors that can occur while decoding.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Error {
-    Truncated,
-    InvalidString,
-    InvalidTag,
-    UnexpectedEof,
-    /// Error for malformed bundle elements (deprecated - bundles can now contain both messages and bundles).
-    NonMessageInBundle,
-}
+#[cfg(feature = "alloc")]
+mod annotate;
+#[cfg(feature = "alloc")]
+mod decode;
+#[cfg(feature = "alloc")]
+mod encode;
+mod error;
+mod options;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "serde")]
+mod serde_args;
+mod view;
 
+#[cfg(feature = "alloc")]
+pub use annotate::{annotate, annotate_with, Annotation, Field, Span};
+#[cfg(feature = "alloc")]
+pub use decode::{
+    decode_bundle, decode_bundle_with, decode_message, decode_message_with, decode_packet,
+    decode_packet_with,
+};
+#[cfg(feature = "alloc")]
+pub use encode::{
+    encode_bundle, encode_into, encode_into_vec, encode_message, encode_packet, encoded_len, Encode,
+};
+pub use error::{Error, ErrorKind};
+pub use options::{DecodeMode, DecodeOptions, Limits};
+#[cfg(feature = "alloc")]
+pub use owned::OwnedPacket;
+#[cfg(feature = "serde")]
+pub use serde_args::{
+    from_args, from_message, from_slice, to_args, to_message, to_vec, ArgsDeserializer, ArgsError,
+    ArgsErrorKind, ArgsSerializer,
+};
+pub use view::{
+    encode_message_into, encoded_message_len, ArgView, Args, BundleView, BundleWriter, Elements,
+    MessageView, PacketView,
+};
+
+/// Result type returned by the decoders and [`encode_into`].
 pub type Result<T> = core::result::Result<T, Error>;
 
 #[inline]
@@ -41,11 +115,80 @@ fn pad4_len(len: usize) -> usize {
     (4 - (len & 3)) & 3
 }
 
-fn put_str(buf: &mut Vec<u8>, s: &str) {
-    buf.extend_from_slice(s.as_bytes());
-    buf.push(0);
-    let pad = pad4_len(s.len() + 1);
-    buf.extend(std::iter::repeat_n(0, pad));
+const ZEROS: [u8; 4] = [0; 4];
+
+mod sink {
+    use crate::Result;
+
+    /// Destination for encoded bytes.
+    ///
+    /// Public only in name, so the sealed `Encode` trait can mention it.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub trait Sink {
+        /// Append `bytes`, failing if they do not fit.
+        fn put(&mut self, bytes: &[u8]) -> Result<()>;
+
+        /// Number of bytes written so far.
+        fn position(&self) -> usize;
+
+        /// Overwrite four bytes written earlier at `at`.
+        fn patch(&mut self, at: usize, bytes: [u8; 4]);
+    }
+}
+
+use sink::Sink;
+
+/// Counts the bytes written to it.
+struct Counter(usize);
+
+impl Sink for Counter {
+    fn put(&mut self, bytes: &[u8]) -> Result<()> {
+        self.0 += bytes.len();
+        Ok(())
+    }
+
+    fn position(&self) -> usize {
+        self.0
+    }
+
+    fn patch(&mut self, _at: usize, _bytes: [u8; 4]) {}
+}
+
+/// Writes into a fixed-size buffer.
+#[derive(Debug)]
+struct SliceSink<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+}
+
+impl Sink for SliceSink<'_> {
+    fn put(&mut self, bytes: &[u8]) -> Result<()> {
+        let Some(dst) = self.buf.get_mut(self.len..self.len + bytes.len()) else {
+            return Err(Error::new(ErrorKind::BufferTooSmall, self.len));
+        };
+        dst.copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    fn position(&self) -> usize {
+        self.len
+    }
+
+    fn patch(&mut self, at: usize, bytes: [u8; 4]) {
+        self.buf[at..at + 4].copy_from_slice(&bytes);
+    }
+}
+
+fn put_str(buf: &mut impl Sink, s: &str) -> Result<()> {
+    buf.put(s.as_bytes())?;
+    put_nul_padding(buf, s.len())
+}
+
+/// Write the NUL terminator and padding after a string of `len` bytes.
+#[inline]
+fn put_nul_padding(buf: &mut impl Sink, len: usize) -> Result<()> {
+    buf.put(&ZEROS[..1 + pad4_len(len + 1)])
 }
 
 fn get_cstr_4(bytes: &[u8], mut off: usize) -> Result<(&str, usize)> {
@@ -55,35 +198,49 @@ fn get_cstr_4(bytes: &[u8], mut off: usize) -> Result<(&str, usize)> {
         off += 1;
     }
     if off >= bytes.len() {
-        return Err(Error::Truncated);
+        return Err(Error::new(ErrorKind::UnterminatedString, start));
     }
-    let s = core::str::from_utf8(&bytes[start..off]).map_err(|_| Error::InvalidString)?;
+    let s = core::str::from_utf8(&bytes[start..off])
+        .map_err(|_| Error::new(ErrorKind::InvalidUtf8, start))?;
     off += 1; // skip NUL
               // Skip padding to 4-byte boundary
     let pad = pad4_len(off - start);
     if off + pad > bytes.len() {
-        return Err(Error::Truncated);
+        return Err(Error::new(ErrorKind::UnexpectedEof, off));
     }
     Ok((s, off + pad))
 }
 
 #[inline]
-fn put_i32(buf: &mut Vec<u8>, v: i32) {
-    let mut tmp = [0u8; 4];
-    BigEndian::write_i32(&mut tmp, v);
-    buf.extend_from_slice(&tmp);
+fn put_i32(buf: &mut impl Sink, v: i32) -> Result<()> {
+    buf.put(&v.to_be_bytes())
 }
 #[inline]
-fn put_f32(buf: &mut Vec<u8>, v: f32) {
-    let mut tmp = [0u8; 4];
-    BigEndian::write_f32(&mut tmp, v);
-    buf.extend_from_slice(&tmp);
+fn put_f32(buf: &mut impl Sink, v: f32) -> Result<()> {
+    buf.put(&v.to_be_bytes())
+}
+
+#[inline]
+fn put_u32(buf: &mut impl Sink, v: u32) -> Result<()> {
+    buf.put(&v.to_be_bytes())
+}
+#[inline]
+fn put_i64(buf: &mut impl Sink, v: i64) -> Result<()> {
+    buf.put(&v.to_be_bytes())
+}
+#[inline]
+fn put_u64(buf: &mut impl Sink, v: u64) -> Result<()> {
+    buf.put(&v.to_be_bytes())
+}
+#[inline]
+fn put_f64(buf: &mut impl Sink, v: f64) -> Result<()> {
+    buf.put(&v.to_be_bytes())
 }
 
 #[inline]
 fn get_i32(bytes: &[u8], off: &mut usize) -> Result<i32> {
     if *off + 4 > bytes.len() {
-        return Err(Error::UnexpectedEof);
+        return Err(Error::new(ErrorKind::UnexpectedEof, *off));
     }
     let v = BigEndian::read_i32(&bytes[*off..*off + 4]);
     *off += 4;
@@ -92,177 +249,50 @@ fn get_i32(bytes: &[u8], off: &mut usize) -> Result<i32> {
 #[inline]
 fn get_f32(bytes: &[u8], off: &mut usize) -> Result<f32> {
     if *off + 4 > bytes.len() {
-        return Err(Error::UnexpectedEof);
+        return Err(Error::new(ErrorKind::UnexpectedEof, *off));
     }
     let v = BigEndian::read_f32(&bytes[*off..*off + 4]);
     *off += 4;
     Ok(v)
 }
 
-/// Encode a single OSC message into bytes.
-pub fn encode_message(msg: &Message<'_>) -> Vec<u8> {
-    let mut buf = Vec::new();
-    put_str(&mut buf, msg.address);
-
-    // Type tag (starts with ',')
-    let mut tag = String::from(",");
-    for a in &msg.args {
-        match a {
-            OscType::Int(_) => tag.push('i'),
-            OscType::Float(_) => tag.push('f'),
-            OscType::String(_) => tag.push('s'),
-            OscType::Blob(_) => tag.push('b'),
-        }
-    }
-    put_str(&mut buf, &tag);
-
-    for a in &msg.args {
-        match a {
-            OscType::Int(v) => put_i32(&mut buf, *v),
-            OscType::Float(v) => put_f32(&mut buf, *v),
-            OscType::String(s) => put_str(&mut buf, s),
-            OscType::Blob(b) => {
-                put_i32(&mut buf, b.len() as i32);
-                buf.extend_from_slice(b);
-                let pad = pad4_len(b.len());
-                buf.extend(std::iter::repeat_n(0, pad));
-            }
-        }
+#[inline]
+fn get_u32(bytes: &[u8], off: &mut usize) -> Result<u32> {
+    if *off + 4 > bytes.len() {
+        return Err(Error::new(ErrorKind::UnexpectedEof, *off));
     }
-    buf
+    let v = BigEndian::read_u32(&bytes[*off..*off + 4]);
+    *off += 4;
+    Ok(v)
 }
-
-/// Decode a single OSC message from bytes, returning the message and number of bytes consumed.
-pub fn decode_message<'a>(bytes: &'a [u8]) -> Result<(Message<'a>, usize)> {
-    let (address, mut off) = get_cstr_4(bytes, 0)?;
-    let (tag, off2) = get_cstr_4(bytes, off)?;
-    off = off2;
-
-    let mut args = Vec::new();
-    let mut chars = tag.chars();
-    if chars.next() != Some(',') {
-        return Err(Error::InvalidTag);
-    }
-
-    for t in chars {
-        match t {
-            'i' => {
-                args.push(OscType::Int(get_i32(bytes, &mut off)?));
-            }
-            'f' => {
-                args.push(OscType::Float(get_f32(bytes, &mut off)?));
-            }
-            's' => {
-                let (s, new_off) = get_cstr_4(bytes, off)?;
-                args.push(OscType::String(s));
-                off = new_off;
-            }
-            'b' => {
-                let len = get_i32(bytes, &mut off)? as usize;
-                if off + len > bytes.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                let blob = &bytes[off..off + len];
-                off += len;
-                let pad = pad4_len(len);
-                if off + pad > bytes.len() {
-                    return Err(Error::UnexpectedEof);
-                }
-                off += pad;
-                args.push(OscType::Blob(blob));
-            }
-            _ => return Err(Error::InvalidTag),
-        }
+#[inline]
+fn get_i64(bytes: &[u8], off: &mut usize) -> Result<i64> {
+    if *off + 8 > bytes.len() {
+        return Err(Error::new(ErrorKind::UnexpectedEof, *off));
     }
-
-    Ok((Message::new(address, args), off))
+    let v = BigEndian::read_i64(&bytes[*off..*off + 8]);
+    *off += 8;
+    Ok(v)
 }
-
-const BUNDLE_TAG: &str = "#bundle";
-
-/// Encode a bundle that can contain messages and nested bundles.
-pub fn encode_bundle(b: &Bundle<'_>) -> Vec<u8> {
-    let mut buf = Vec::new();
-    put_str(&mut buf, BUNDLE_TAG);
-    // 64-bit big-endian NTP timetag
-    let mut tt = [0u8; 8];
-    BigEndian::write_u64(&mut tt, b.timetag);
-    buf.extend_from_slice(&tt);
-
-    for packet in &b.packets {
-        let pkt = match packet {
-            OscPacket::Message(msg) => encode_message(msg),
-            OscPacket::Bundle(bundle) => encode_bundle(bundle),
-        };
-        put_i32(&mut buf, pkt.len() as i32);
-        buf.extend_from_slice(&pkt);
+#[inline]
+fn get_u64(bytes: &[u8], off: &mut usize) -> Result<u64> {
+    if *off + 8 > bytes.len() {
+        return Err(Error::new(ErrorKind::UnexpectedEof, *off));
     }
-    buf
+    let v = BigEndian::read_u64(&bytes[*off..*off + 8]);
+    *off += 8;
+    Ok(v)
 }
-
-/// Decode a bundle that can contain messages and nested bundles. Returns the bundle and number of bytes consumed.
-pub fn decode_bundle<'a>(bytes: &'a [u8]) -> Result<(Bundle<'a>, usize)> {
-    let (tag, mut off) = get_cstr_4(bytes, 0)?;
-    if tag != BUNDLE_TAG {
-        return Err(Error::InvalidString);
-    }
-    if off + 8 > bytes.len() {
-        return Err(Error::Truncated);
-    }
-    let timetag = BigEndian::read_u64(&bytes[off..off + 8]);
-    off += 8;
-
-    let mut packets = Vec::new();
-    while off < bytes.len() {
-        let size = get_i32(bytes, &mut off)? as usize;
-        if off + size > bytes.len() {
-            return Err(Error::Truncated);
-        }
-
-        let element_bytes = &bytes[off..off + size];
-
-        // Try to determine if this is a bundle by checking if it has a valid bundle structure
-        // A bundle must have at minimum: "#bundle\0" (8 bytes aligned) + 8-byte timetag = 16 bytes
-        let is_bundle =
-            if element_bytes.len() >= 16 && element_bytes.starts_with(BUNDLE_TAG.as_bytes()) {
-                // Check if it's properly null-terminated and 4-byte aligned like a real bundle
-                let tag_end = BUNDLE_TAG.len();
-                element_bytes.get(tag_end) == Some(&0) && {
-                    // Calculate where the timetag should start (after null-terminated "#bundle" + padding)
-                    let tag_with_null_len = tag_end + 1;
-                    let padding = pad4_len(tag_with_null_len);
-                    let timetag_start = tag_with_null_len + padding;
-                    // Ensure we have enough bytes for the timetag
-                    element_bytes.len() >= timetag_start + 8
-                }
-            } else {
-                false
-            };
-
-        if is_bundle {
-            // Try to decode as bundle first, fall back to message if it fails
-            match decode_bundle(element_bytes) {
-                Ok((bundle, used)) if used == size => {
-                    packets.push(OscPacket::Bundle(bundle));
-                }
-                _ => {
-                    // Bundle decoding failed, treat as message
-                    let (msg, used) = decode_message(element_bytes)?;
-                    if used != size {
-                        return Err(Error::InvalidTag);
-                    }
-                    packets.push(OscPacket::Message(msg));
-                }
-            }
-        } else {
-            let (msg, used) = decode_message(element_bytes)?;
-            if used != size {
-                return Err(Error::InvalidTag);
-            }
-            packets.push(OscPacket::Message(msg));
-        }
-
-        off += size;
+#[inline]
+fn get_f64(bytes: &[u8], off: &mut usize) -> Result<f64> {
+    if *off + 8 > bytes.len() {
+        return Err(Error::new(ErrorKind::UnexpectedEof, *off));
     }
-    Ok((Bundle::new(timetag, packets), off))
+    let v = BigEndian::read_f64(&bytes[*off..*off + 8]);
+    *off += 8;
+    Ok(v)
 }
+
+const BUNDLE_TAG: &str = "#bundle";
+/// The encoded `#bundle` string including its NUL terminator.
+const BUNDLE_PREFIX: &[u8] = b"#bundle\0";
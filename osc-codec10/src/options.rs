@@ -0,0 +1,189 @@
+//! Decoder configuration.
+
+/// Upper bounds the decoder enforces on untrusted input.
+///
+/// Every limit is checked before the corresponding data is decoded or
+/// allocated, so a hostile packet is rejected without using more stack or heap
+/// than the limits allow. Exceeding a limit yields the matching
+/// [`ErrorKind`](crate::ErrorKind).
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use osc_codec10::{decode_message_with, encode_message, DecodeOptions, ErrorKind, Limits};
+/// use osc_types10::{Message, OscType};
+///
+/// let bytes = encode_message(&Message::new("/a", vec![OscType::Int(1), OscType::Int(2)]));
+/// let options = DecodeOptions::new().with_limits(Limits { max_args: 1, ..Limits::default() });
+///
+/// let err = decode_message_with(&bytes, &options).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::TooManyArguments);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum nesting depth of bundles and of arrays.
+    ///
+    /// The elements of a top-level bundle are at depth 1, the elements of a
+    /// bundle inside it at depth 2, and so on. Arrays are counted the same way
+    /// within each message.
+    pub max_depth: usize,
+    /// Maximum number of type tags in one message, array brackets included.
+    pub max_args: usize,
+    /// Maximum size in bytes of one blob argument.
+    pub max_blob_size: usize,
+    /// Maximum length in bytes, without the NUL terminator, of an address or
+    /// string argument.
+    pub max_string_size: usize,
+    /// Maximum number of bundle elements in one packet, counted over all
+    /// nested bundles.
+    pub max_elements: usize,
+}
+
+impl Limits {
+    /// No limits beyond the size of the input.
+    ///
+    /// Only use this for trusted input: nesting depth is then bounded by the
+    /// input size alone, and deep nesting can exhaust the stack.
+    pub const UNLIMITED: Limits = Limits {
+        max_depth: usize::MAX,
+        max_args: usize::MAX,
+        max_blob_size: usize::MAX,
+        max_string_size: usize::MAX,
+        max_elements: usize::MAX,
+    };
+}
+
+impl Default for Limits {
+    /// Limits that accept any packet a typical application sends while keeping
+    /// recursion and allocation bounded.
+    fn default() -> Self {
+        Limits {
+            max_depth: 32,
+            max_args: 1024,
+            max_blob_size: 1 << 20,
+            max_string_size: 4096,
+            max_elements: 4096,
+        }
+    }
+}
+
+/// How closely the decoder holds packets to the OSC 1.0 spec.
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use osc_codec10::{decode_message_with, DecodeMode, DecodeOptions, ErrorKind};
+/// use osc_types10::OscType;
+///
+/// // A legacy message without a type tag string, padded with garbage.
+/// let bytes = b"/a\0x\0\0\0\x2a";
+///
+/// let strict = DecodeOptions::new().with_mode(DecodeMode::Strict);
+/// let err = decode_message_with(bytes, &strict).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::NonZeroPadding);
+///
+/// let lenient = DecodeOptions::new().with_mode(DecodeMode::Lenient);
+/// let (msg, _) = decode_message_with(bytes, &lenient).unwrap();
+/// assert_eq!(msg.args, [OscType::Blob(b"\0\0\0\x2a")]);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Reject anything that does not conform to the spec, for validating
+    /// senders. On top of the [`Standard`](DecodeMode::Standard) checks:
+    ///
+    /// - padding bytes after strings and blobs must be zero
+    ///   ([`ErrorKind::NonZeroPadding`](crate::ErrorKind::NonZeroPadding));
+    /// - addresses must be ASCII
+    ///   ([`ErrorKind::NonAsciiAddress`](crate::ErrorKind::NonAsciiAddress));
+    /// - [`decode_message_with`](crate::decode_message_with) rejects bytes
+    ///   after the message
+    ///   ([`ErrorKind::TrailingBytes`](crate::ErrorKind::TrailingBytes)).
+    Strict,
+    /// Decode everything the spec describes, without checking the value of
+    /// padding bytes.
+    #[default]
+    Standard,
+    /// Accept packets from legacy senders that [`Standard`](DecodeMode::Standard)
+    /// rejects:
+    ///
+    /// - a message without a type tag string decodes with a single blob
+    ///   argument holding the raw bytes after the address, or with no
+    ///   arguments if there are none;
+    /// - bytes after a message are ignored, both at the end of a packet and
+    ///   at the end of a bundle element.
+    ///
+    /// Padding bytes are not checked, as in `Standard`.
+    Lenient,
+}
+
+/// Options for the `decode_*_with` functions.
+///
+/// The plain `decode_*` functions use [`DecodeOptions::default`]: default
+/// [`Limits`], [`DecodeMode::Standard`] and spec-exact bundle element detection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    limits: Limits,
+    mode: DecodeMode,
+    bundle_fallback: bool,
+}
+
+impl DecodeOptions {
+    /// Options with the default [`Limits`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the limits.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// The limits these options enforce.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Set the decode mode.
+    pub fn with_mode(mut self, mode: DecodeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The decode mode.
+    pub fn mode(&self) -> DecodeMode {
+        self.mode
+    }
+
+    /// Decode bundle elements that start with `#bundle` but are not valid
+    /// bundles as messages instead of failing.
+    ///
+    /// Per the OSC 1.0 spec an element is a bundle if and only if it starts
+    /// with the `#bundle` string, and that is what the decoder does by default.
+    /// Some senders nevertheless put messages addressed to `#bundle` into
+    /// bundles; enable this to accept them. If the element is not a valid
+    /// message either, the bundle error is reported.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use osc_codec10::{decode_bundle, decode_bundle_with, encode_bundle, DecodeOptions};
+    /// use osc_types10::{Bundle, Message, OscPacket};
+    ///
+    /// let bytes = encode_bundle(&Bundle::with_messages(1, vec![Message::new("#bundle", vec![])]));
+    /// assert!(decode_bundle(&bytes).is_err());
+    ///
+    /// let options = DecodeOptions::new().with_bundle_fallback(true);
+    /// let (bundle, _) = decode_bundle_with(&bytes, &options).unwrap();
+    /// assert!(matches!(&bundle.packets[0], OscPacket::Message(msg) if msg.address == "#bundle"));
+    /// # }
+    /// ```
+    pub fn with_bundle_fallback(mut self, enabled: bool) -> Self {
+        self.bundle_fallback = enabled;
+        self
+    }
+
+    /// Whether [`DecodeOptions::with_bundle_fallback`] is enabled.
+    pub fn bundle_fallback(&self) -> bool {
+        self.bundle_fallback
+    }
+}
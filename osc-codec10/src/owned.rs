@@ -0,0 +1,159 @@
+//! Decoded packets that keep their receive buffer alive.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+use osc_types10::OscPacket;
+use self_cell::self_cell;
+
+use crate::{decode_packet_with, DecodeOptions, Result};
+
+/// Buffer types an [`OwnedPacket`] can take ownership of.
+enum Buffer {
+    Vec(Vec<u8>),
+    #[cfg(feature = "bytes")]
+    Bytes(bytes::Bytes),
+}
+
+impl Buffer {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            Buffer::Vec(v) => v,
+            #[cfg(feature = "bytes")]
+            Buffer::Bytes(b) => b,
+        }
+    }
+}
+
+self_cell!(
+    struct Cell {
+        owner: Buffer,
+
+        #[covariant]
+        dependent: OscPacket,
+    }
+);
+
+/// A decoded packet bundled with the buffer it borrows from.
+///
+/// Strings and blobs in [`OwnedPacket::packet`] still point into the original
+/// receive buffer, so no argument data is copied, yet the whole value is
+/// `'static` and can be moved across threads and channels.
+///
+/// ```
+/// use osc_codec10::{encode_message, OwnedPacket};
+/// use osc_types10::{Message, OscPacket, OscType};
+///
+/// let bytes = encode_message(&Message::new("/ping", vec![OscType::String("pong")]));
+/// let owned = OwnedPacket::from_vec(bytes).unwrap();
+///
+/// let handle = std::thread::spawn(move || match owned.packet() {
+///     OscPacket::Message(msg) => msg.address.len(),
+///     OscPacket::Bundle(_) => 0,
+/// });
+/// assert_eq!(handle.join().unwrap(), 5);
+/// ```
+pub struct OwnedPacket {
+    cell: Cell,
+    options: DecodeOptions,
+}
+
+impl OwnedPacket {
+    /// Decode `buf` as a message or bundle and take ownership of it.
+    pub fn from_vec(buf: Vec<u8>) -> Result<Self> {
+        Self::from_vec_with(buf, &DecodeOptions::default())
+    }
+
+    /// Like [`OwnedPacket::from_vec`], enforcing the limits in `options`.
+    pub fn from_vec_with(buf: Vec<u8>, options: &DecodeOptions) -> Result<Self> {
+        Self::new(Buffer::Vec(buf), options)
+    }
+
+    /// Decode `buf` as a message or bundle and take ownership of it.
+    #[cfg(feature = "bytes")]
+    pub fn from_bytes(buf: bytes::Bytes) -> Result<Self> {
+        Self::from_bytes_with(buf, &DecodeOptions::default())
+    }
+
+    /// Like [`OwnedPacket::from_bytes`], enforcing the limits in `options`.
+    #[cfg(feature = "bytes")]
+    pub fn from_bytes_with(buf: bytes::Bytes, options: &DecodeOptions) -> Result<Self> {
+        Self::new(Buffer::Bytes(buf), options)
+    }
+
+    fn new(buf: Buffer, options: &DecodeOptions) -> Result<Self> {
+        let cell = Cell::try_new(buf, |buf| decode_packet_with(buf.as_slice(), options))?;
+        Ok(Self {
+            cell,
+            options: *options,
+        })
+    }
+
+    /// The decoded packet, borrowing from the owned buffer.
+    pub fn packet(&self) -> &OscPacket<'_> {
+        self.cell.borrow_dependent()
+    }
+
+    /// The raw bytes the packet was decoded from.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.cell.borrow_owner().as_slice()
+    }
+
+    /// Drop the decoded packet and return the buffer as a `Vec<u8>`.
+    ///
+    /// A `Bytes` buffer is copied only if it is shared.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.cell.into_owner() {
+            Buffer::Vec(v) => v,
+            #[cfg(feature = "bytes")]
+            Buffer::Bytes(b) => Vec::from(b),
+        }
+    }
+}
+
+impl fmt::Debug for OwnedPacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedPacket")
+            .field("packet", self.packet())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clone for OwnedPacket {
+    /// Clones the buffer and decodes the copy again.
+    fn clone(&self) -> Self {
+        let buf = match self.cell.borrow_owner() {
+            Buffer::Vec(v) => Buffer::Vec(v.clone()),
+            #[cfg(feature = "bytes")]
+            Buffer::Bytes(b) => Buffer::Bytes(b.clone()),
+        };
+        Self::new(buf, &self.options).expect("buffer already decoded successfully")
+    }
+}
+
+impl PartialEq for OwnedPacket {
+    fn eq(&self, other: &Self) -> bool {
+        self.packet() == other.packet()
+    }
+}
+
+impl TryFrom<Vec<u8>> for OwnedPacket {
+    type Error = crate::Error;
+
+    fn try_from(buf: Vec<u8>) -> Result<Self> {
+        Self::from_vec(buf)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl TryFrom<bytes::Bytes> for OwnedPacket {
+    type Error = crate::Error;
+
+    fn try_from(buf: bytes::Bytes) -> Result<Self> {
+        Self::from_bytes(buf)
+    }
+}
@@ -0,0 +1,900 @@
+//! A serde data format mapping Rust values to OSC argument lists; the layout
+//! is documented at the crate root.
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use osc_types10::{Message, MessageBuf, OscType, OscTypeBuf, TimeTag};
+use serde::de::{self, value::BorrowedStrDeserializer, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::{ser, Deserialize, Serialize};
+
+use crate::{decode_message, encode_packet, Error, ErrorKind};
+
+/// Name of the newtype struct `TimeTag` serializes its wire value as, which
+/// this format maps to the `t` argument.
+const TIME_TAG: &str = "TimeTag";
+
+/// What went wrong while mapping a value to or from OSC arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsErrorKind {
+    /// A `Serialize` or `Deserialize` impl reported an error.
+    Custom(String),
+    /// The value has no OSC representation, e.g. a 128-bit integer.
+    Unsupported(&'static str),
+    /// An integer does not fit the `h` argument or the target type.
+    OutOfRange,
+    /// An argument has a type tag the Rust type does not accept.
+    WrongType {
+        /// What the Rust type expected.
+        expected: &'static str,
+        /// The argument's type tag.
+        actual: char,
+    },
+    /// There are fewer arguments than the value needs.
+    Missing,
+    /// Arguments were left over after the value was read.
+    Extra {
+        /// The first left-over argument's type tag.
+        actual: char,
+    },
+    /// The bytes passed to [`from_slice`] are not a valid message.
+    Decode(Error),
+}
+
+/// Error returned by [`to_args`], [`from_args`] and the related functions.
+///
+/// ```
+/// use osc_codec10::{from_args, ArgsErrorKind};
+/// use osc_types10::OscType;
+///
+/// let err = from_args::<(i32, f32)>(&[OscType::Int(1), OscType::Int(2)]).unwrap_err();
+/// assert_eq!(
+///     err.kind(),
+///     &ArgsErrorKind::WrongType { expected: "f", actual: 'i' }
+/// );
+/// assert_eq!(err.index(), Some(1));
+/// assert_eq!(err.to_string(), "expected type tag 'f', found 'i' at argument 1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgsError {
+    kind: ArgsErrorKind,
+    index: Option<usize>,
+}
+
+impl ArgsError {
+    fn new(kind: ArgsErrorKind) -> Self {
+        Self { kind, index: None }
+    }
+
+    /// Attach the argument index, unless one is already recorded.
+    fn at(mut self, index: usize) -> Self {
+        self.index.get_or_insert(index);
+        self
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &ArgsErrorKind {
+        &self.kind
+    }
+
+    /// Index of the top-level argument being read, if the error concerns one.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ArgsErrorKind::Custom(message) => f.write_str(message)?,
+            ArgsErrorKind::Unsupported(what) => write!(f, "{what} has no OSC representation")?,
+            ArgsErrorKind::OutOfRange => f.write_str("integer out of range")?,
+            ArgsErrorKind::WrongType { expected, actual } => {
+                f.write_str("expected type tag ")?;
+                for (i, tag) in expected.chars().enumerate() {
+                    if i > 0 {
+                        f.write_str(" or ")?;
+                    }
+                    write!(f, "{tag:?}")?;
+                }
+                write!(f, ", found {actual:?}")?;
+            }
+            ArgsErrorKind::Missing => f.write_str("missing argument")?,
+            ArgsErrorKind::Extra { actual } => write!(f, "unexpected argument {actual:?}")?,
+            ArgsErrorKind::Decode(err) => write!(f, "{err}")?,
+        }
+        if let Some(index) = self.index {
+            write!(f, " at argument {index}")?;
+        }
+        Ok(())
+    }
+}
+
+// `std::error::Error` with `std`, and serde's stand-in for it without.
+impl ser::StdError for ArgsError {
+    #[cfg(feature = "std")]
+    fn source(&self) -> Option<&(dyn ser::StdError + 'static)> {
+        match &self.kind {
+            ArgsErrorKind::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl ser::Error for ArgsError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::new(ArgsErrorKind::Custom(msg.to_string()))
+    }
+}
+
+impl de::Error for ArgsError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::new(ArgsErrorKind::Custom(msg.to_string()))
+    }
+}
+
+impl From<Error> for ArgsError {
+    fn from(err: Error) -> Self {
+        Self::new(ArgsErrorKind::Decode(err))
+    }
+}
+
+/// Serialize `value` into a list of arguments.
+///
+/// ```
+/// use osc_codec10::to_args;
+/// use osc_types10::OscTypeBuf;
+///
+/// let args = to_args(&(440.0f32, "sine", vec![1, 2])).unwrap();
+/// assert_eq!(
+///     args,
+///     [
+///         OscTypeBuf::Float(440.0),
+///         OscTypeBuf::String("sine".into()),
+///         OscTypeBuf::Array(vec![OscTypeBuf::Int(1), OscTypeBuf::Int(2)]),
+///     ]
+/// );
+/// ```
+pub fn to_args<T: Serialize + ?Sized>(value: &T) -> Result<Vec<OscTypeBuf>, ArgsError> {
+    let mut serializer = ArgsSerializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_args())
+}
+
+/// Serialize `value` into the arguments of a message to `address`.
+pub fn to_message<T: Serialize + ?Sized>(
+    address: &str,
+    value: &T,
+) -> Result<MessageBuf, ArgsError> {
+    Ok(MessageBuf::new(address, to_args(value)?))
+}
+
+/// Serialize `value` into an encoded message to `address`.
+///
+/// The bytes are the same as [`encode_packet`] on [`to_message`]'s result.
+pub fn to_vec<T: Serialize + ?Sized>(address: &str, value: &T) -> Result<Vec<u8>, ArgsError> {
+    Ok(encode_packet(&to_message(address, value)?))
+}
+
+/// Deserialize a `T` from a list of arguments, which it must use up.
+pub fn from_args<'a, T: Deserialize<'a>>(args: &[OscType<'a>]) -> Result<T, ArgsError> {
+    let mut deserializer = ArgsDeserializer::new(args);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserialize a `T` from the arguments of `msg`; the address is not checked.
+pub fn from_message<'a, T: Deserialize<'a>>(msg: &Message<'a>) -> Result<T, ArgsError> {
+    from_args(&msg.args)
+}
+
+/// Decode the message in `bytes` and deserialize a `T` from its arguments.
+///
+/// The address is ignored; to dispatch on it, decode with [`decode_message`]
+/// and call [`from_message`]. Strings and blobs in `T` may borrow from `bytes`.
+///
+/// ```
+/// use osc_codec10::{from_slice, to_vec};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Note<'a> {
+///     pitch: i32,
+///     velocity: f32,
+///     instrument: &'a str,
+/// }
+///
+/// let bytes = to_vec("/note", &Note { pitch: 60, velocity: 0.5, instrument: "piano" }).unwrap();
+/// assert_eq!(&bytes[8..12], b",ifs");
+/// let note: Note<'_> = from_slice(&bytes).unwrap();
+/// assert_eq!(note, Note { pitch: 60, velocity: 0.5, instrument: "piano" });
+/// ```
+pub fn from_slice<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, ArgsError> {
+    let (msg, used) = decode_message(bytes)?;
+    if used != bytes.len() {
+        return Err(Error::new(ErrorKind::TrailingBytes, used).into());
+    }
+    from_message(&msg)
+}
+
+/// A serde `Serializer` that appends OSC arguments to a list.
+///
+/// Serializing several values into one `ArgsSerializer` concatenates their
+/// arguments.
+#[derive(Debug, Default)]
+pub struct ArgsSerializer {
+    args: Vec<OscTypeBuf>,
+    /// Serializing the contents of a `TimeTag` newtype, whose `u64` is a `t`.
+    time_tag: bool,
+}
+
+impl ArgsSerializer {
+    /// Create a serializer with an empty argument list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The arguments serialized so far.
+    pub fn into_args(self) -> Vec<OscTypeBuf> {
+        self.args
+    }
+
+    fn push(&mut self, arg: OscTypeBuf) -> Result<(), ArgsError> {
+        self.args.push(arg);
+        Ok(())
+    }
+}
+
+impl<'s> ser::Serializer for &'s mut ArgsSerializer {
+    type Ok = ();
+    type Error = ArgsError;
+    type SerializeSeq = ArraySerializer<'s>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = ArraySerializer<'s>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), ArgsError> {
+        self.serialize_i32(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), ArgsError> {
+        self.serialize_i32(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Int(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Long(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), ArgsError> {
+        self.serialize_i32(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), ArgsError> {
+        self.serialize_i32(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), ArgsError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), ArgsError> {
+        if self.time_tag {
+            return self.push(OscTypeBuf::Time(TimeTag::from_bits(v)));
+        }
+        let v = i64::try_from(v).map_err(|_| ArgsError::new(ArgsErrorKind::OutOfRange))?;
+        self.serialize_i64(v)
+    }
+
+    fn serialize_i128(self, _v: i128) -> Result<(), ArgsError> {
+        Err(ArgsError::new(ArgsErrorKind::Unsupported("i128")))
+    }
+
+    fn serialize_u128(self, _v: u128) -> Result<(), ArgsError> {
+        Err(ArgsError::new(ArgsErrorKind::Unsupported("u128")))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Float(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Double(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::String(v.into()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Blob(v.into()))
+    }
+
+    fn serialize_none(self) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Nil)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), ArgsError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), ArgsError> {
+        self.push(OscTypeBuf::Nil)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), ArgsError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), ArgsError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), ArgsError> {
+        if name == TIME_TAG {
+            let mut inner = ArgsSerializer {
+                time_tag: true,
+                ..ArgsSerializer::new()
+            };
+            value.serialize(&mut inner)?;
+            self.args.append(&mut inner.args);
+            return Ok(());
+        }
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), ArgsError> {
+        self.serialize_str(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<ArraySerializer<'s>, ArgsError> {
+        Ok(ArraySerializer {
+            parent: self,
+            items: ArgsSerializer::new(),
+        })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, ArgsError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, ArgsError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, ArgsError> {
+        self.serialize_str(variant)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<ArraySerializer<'s>, ArgsError> {
+        self.serialize_seq(len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, ArgsError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, ArgsError> {
+        self.serialize_str(variant)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Flattens tuples, structs and enum variant fields into the current list.
+macro_rules! flatten {
+    ($($trait:ident :: $method:ident($($key:ident),*);)*) => {$(
+        impl ser::$trait for &mut ArgsSerializer {
+            type Ok = ();
+            type Error = ArgsError;
+
+            fn $method<T: Serialize + ?Sized>(
+                &mut self,
+                $($key: &'static str,)*
+                value: &T,
+            ) -> Result<(), ArgsError> {
+                $(let _ = $key;)*
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<(), ArgsError> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+flatten! {
+    SerializeTuple::serialize_element();
+    SerializeTupleStruct::serialize_field();
+    SerializeTupleVariant::serialize_field();
+    SerializeStruct::serialize_field(key);
+    SerializeStructVariant::serialize_field(key);
+}
+
+/// Collects a sequence or map into an array argument.
+#[derive(Debug)]
+pub struct ArraySerializer<'s> {
+    parent: &'s mut ArgsSerializer,
+    items: ArgsSerializer,
+}
+
+impl ser::SerializeSeq for ArraySerializer<'_> {
+    type Ok = ();
+    type Error = ArgsError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ArgsError> {
+        value.serialize(&mut self.items)
+    }
+
+    fn end(self) -> Result<(), ArgsError> {
+        self.parent.push(OscTypeBuf::Array(self.items.args))
+    }
+}
+
+impl ser::SerializeMap for ArraySerializer<'_> {
+    type Ok = ();
+    type Error = ArgsError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), ArgsError> {
+        key.serialize(&mut self.items)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ArgsError> {
+        value.serialize(&mut self.items)
+    }
+
+    fn end(self) -> Result<(), ArgsError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// A serde `Deserializer` that reads values from a list of OSC arguments.
+#[derive(Debug)]
+pub struct ArgsDeserializer<'b, 'a> {
+    args: &'b [OscType<'a>],
+    pos: usize,
+    /// Index of the enclosing top-level argument when reading an array.
+    outer: Option<usize>,
+}
+
+impl<'b, 'a> ArgsDeserializer<'b, 'a> {
+    /// Create a deserializer reading `args` from the start.
+    pub fn new(args: &'b [OscType<'a>]) -> Self {
+        Self {
+            args,
+            pos: 0,
+            outer: None,
+        }
+    }
+
+    /// Check that every argument has been read.
+    pub fn end(&self) -> Result<(), ArgsError> {
+        match self.args.get(self.pos) {
+            Some(arg) => Err(self.error(ArgsErrorKind::Extra { actual: arg.tag() })),
+            None => Ok(()),
+        }
+    }
+
+    fn error(&self, kind: ArgsErrorKind) -> ArgsError {
+        ArgsError::new(kind).at(self.outer.unwrap_or(self.pos))
+    }
+
+    fn peek(&self) -> Option<&'b OscType<'a>> {
+        self.args.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<&'b OscType<'a>, ArgsError> {
+        let arg = self
+            .peek()
+            .ok_or_else(|| self.error(ArgsErrorKind::Missing))?;
+        self.pos += 1;
+        Ok(arg)
+    }
+
+    /// Report that the argument just read has the wrong type.
+    fn wrong_type(&mut self, expected: &'static str, arg: &OscType<'_>) -> ArgsError {
+        self.pos -= 1;
+        self.error(ArgsErrorKind::WrongType {
+            expected,
+            actual: arg.tag(),
+        })
+    }
+
+    fn integer<T: TryFrom<i64>>(&mut self) -> Result<T, ArgsError> {
+        let value = match *self.next()? {
+            OscType::Int(v) => v.into(),
+            OscType::Long(v) => v,
+            ref arg => return Err(self.wrong_type("ih", arg)),
+        };
+        T::try_from(value).map_err(|_| {
+            self.pos -= 1;
+            self.error(ArgsErrorKind::OutOfRange)
+        })
+    }
+
+    fn str(&mut self) -> Result<&'a str, ArgsError> {
+        match *self.next()? {
+            OscType::String(s) | OscType::Symbol(s) => Ok(s),
+            ref arg => Err(self.wrong_type("sS", arg)),
+        }
+    }
+
+    /// A deserializer for the items of the array argument just read.
+    fn array(&self, items: &'b [OscType<'a>]) -> ArgsDeserializer<'b, 'a> {
+        ArgsDeserializer {
+            args: items,
+            pos: 0,
+            outer: Some(self.outer.unwrap_or(self.pos - 1)),
+        }
+    }
+
+    fn array_items(&mut self) -> Result<&'b [OscType<'a>], ArgsError> {
+        match self.next()? {
+            OscType::Array(items) => Ok(items),
+            arg => Err(self.wrong_type("[", arg)),
+        }
+    }
+}
+
+macro_rules! deserialize_integers {
+    ($($method:ident => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+            visitor.$visit(self.integer()?)
+        }
+    )*};
+}
+
+impl<'a> de::Deserializer<'a> for &mut ArgsDeserializer<'_, 'a> {
+    type Error = ArgsError;
+
+    fn deserialize_any<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match *self.next()? {
+            OscType::Int(v) => visitor.visit_i32(v),
+            OscType::Float(v) => visitor.visit_f32(v),
+            OscType::String(s) | OscType::Symbol(s) => visitor.visit_borrowed_str(s),
+            OscType::Blob(b) => visitor.visit_borrowed_bytes(b),
+            OscType::Long(v) => visitor.visit_i64(v),
+            OscType::Time(t) => visitor.visit_u64(t.to_bits()),
+            OscType::Double(v) => visitor.visit_f64(v),
+            OscType::Char(c) => visitor.visit_char(c),
+            OscType::Color(c) => {
+                visitor.visit_u32(u32::from_be_bytes([c.red, c.green, c.blue, c.alpha]))
+            }
+            OscType::Midi(m) => {
+                visitor.visit_u32(u32::from_be_bytes([m.port, m.status, m.data1, m.data2]))
+            }
+            OscType::Bool(v) => visitor.visit_bool(v),
+            OscType::Nil | OscType::Inf => visitor.visit_unit(),
+            OscType::Array(ref items) => {
+                let mut items = self.array(items);
+                let value = visitor.visit_seq(Items(&mut items))?;
+                items.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match *self.next()? {
+            OscType::Bool(v) => visitor.visit_bool(v),
+            ref arg => Err(self.wrong_type("TF", arg)),
+        }
+    }
+
+    deserialize_integers! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    fn deserialize_f32<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match *self.next()? {
+            OscType::Float(v) => visitor.visit_f32(v),
+            ref arg => Err(self.wrong_type("f", arg)),
+        }
+    }
+
+    fn deserialize_f64<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match *self.next()? {
+            OscType::Float(v) => visitor.visit_f64(v.into()),
+            OscType::Double(v) => visitor.visit_f64(v),
+            ref arg => Err(self.wrong_type("fd", arg)),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match *self.next()? {
+            OscType::Char(c) => visitor.visit_char(c),
+            ref arg => Err(self.wrong_type("c", arg)),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        visitor.visit_borrowed_str(self.str()?)
+    }
+
+    fn deserialize_string<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match *self.next()? {
+            OscType::Blob(b) => visitor.visit_borrowed_bytes(b),
+            ref arg => Err(self.wrong_type("b", arg)),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match self.peek() {
+            None => visitor.visit_none(),
+            Some(OscType::Nil) => {
+                self.pos += 1;
+                visitor.visit_none()
+            }
+            Some(_) => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        match *self.next()? {
+            OscType::Nil => visitor.visit_unit(),
+            ref arg => Err(self.wrong_type("N", arg)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ArgsError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'a>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ArgsError> {
+        if name != TIME_TAG {
+            return visitor.visit_newtype_struct(self);
+        }
+        match *self.next()? {
+            OscType::Time(t) => visitor.visit_newtype_struct(t.to_bits().into_deserializer()),
+            ref arg => Err(self.wrong_type("t", arg)),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        let items = self.array_items()?;
+        let mut items = self.array(items);
+        let value = visitor.visit_seq(Items(&mut items))?;
+        items.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'a>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ArgsError> {
+        visitor.visit_seq(Fields { de: self, len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ArgsError> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        let items = self.array_items()?;
+        let mut items = self.array(items);
+        let value = visitor.visit_map(Items(&mut items))?;
+        items.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ArgsError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ArgsError> {
+        let variant = self.str()?;
+        visitor.visit_enum(Variant { de: self, variant })
+    }
+
+    fn deserialize_identifier<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, ArgsError> {
+        self.next()?;
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// The items of an array, read until it is exhausted.
+struct Items<'d, 'b, 'a>(&'d mut ArgsDeserializer<'b, 'a>);
+
+impl<'a> de::SeqAccess<'a> for Items<'_, '_, 'a> {
+    type Error = ArgsError;
+
+    fn next_element_seed<T: DeserializeSeed<'a>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, ArgsError> {
+        if self.0.peek().is_none() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.0).map(Some)
+    }
+}
+
+impl<'a> de::MapAccess<'a> for Items<'_, '_, 'a> {
+    type Error = ArgsError;
+
+    fn next_key_seed<K: DeserializeSeed<'a>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ArgsError> {
+        de::SeqAccess::next_element_seed(self, seed)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'a>>(&mut self, seed: V) -> Result<V::Value, ArgsError> {
+        seed.deserialize(&mut *self.0)
+    }
+}
+
+/// A fixed number of flattened fields.
+struct Fields<'d, 'b, 'a> {
+    de: &'d mut ArgsDeserializer<'b, 'a>,
+    len: usize,
+}
+
+impl<'a> de::SeqAccess<'a> for Fields<'_, '_, 'a> {
+    type Error = ArgsError;
+
+    fn next_element_seed<T: DeserializeSeed<'a>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, ArgsError> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+/// An enum variant: its name, then its flattened fields.
+struct Variant<'d, 'b, 'a> {
+    de: &'d mut ArgsDeserializer<'b, 'a>,
+    variant: &'a str,
+}
+
+impl<'d, 'b, 'a> de::EnumAccess<'a> for Variant<'d, 'b, 'a> {
+    type Error = ArgsError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'a>>(self, seed: V) -> Result<(V::Value, Self), ArgsError> {
+        let name = BorrowedStrDeserializer::<ArgsError>::new(self.variant);
+        let value = seed.deserialize(name).map_err(|err| {
+            // The variant name is the argument before the fields.
+            err.at(self.de.outer.unwrap_or(self.de.pos - 1))
+        })?;
+        Ok((value, self))
+    }
+}
+
+impl<'a> de::VariantAccess<'a> for Variant<'_, '_, 'a> {
+    type Error = ArgsError;
+
+    fn unit_variant(self) -> Result<(), ArgsError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'a>>(self, seed: T) -> Result<T::Value, ArgsError> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'a>>(self, len: usize, visitor: V) -> Result<V::Value, ArgsError> {
+        visitor.visit_seq(Fields { de: self.de, len })
+    }
+
+    fn struct_variant<V: Visitor<'a>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ArgsError> {
+        self.tuple_variant(fields.len(), visitor)
+    }
+}
@@ -0,0 +1,692 @@
+//! Zero-copy views over encoded packets that decode arguments on demand.
+
+use osc_types10::{Color, MidiMessage, TimeTag};
+#[cfg(feature = "alloc")]
+use osc_types10::{Message, OscType};
+
+use crate::{
+    get_cstr_4, get_f32, get_f64, get_i32, get_i64, get_u32, get_u64, pad4_len, put_f32, put_f64,
+    put_i32, put_i64, put_nul_padding, put_str, put_u32, put_u64, Counter, Error, ErrorKind,
+    Limits, Result, Sink, SliceSink, BUNDLE_PREFIX, BUNDLE_TAG, ZEROS,
+};
+
+/// A single argument read from a [`MessageView`].
+///
+/// Mirrors `OscType`, except that arrays are iterated lazily instead of
+/// being collected into a `Vec`.
+#[derive(Debug, Clone)]
+pub enum ArgView<'a> {
+    /// `i`: 32-bit integer.
+    Int(i32),
+    /// `f`: 32-bit float.
+    Float(f32),
+    /// `s`: string.
+    String(&'a str),
+    /// `b`: blob.
+    Blob(&'a [u8]),
+    /// `h`: 64-bit integer.
+    Long(i64),
+    /// `t`: time tag.
+    Time(TimeTag),
+    /// `d`: 64-bit float.
+    Double(f64),
+    /// `S`: symbol.
+    Symbol(&'a str),
+    /// `c`: character.
+    Char(char),
+    /// `r`: RGBA color.
+    Color(Color),
+    /// `m`: MIDI message.
+    Midi(MidiMessage),
+    /// `T` or `F`.
+    Bool(bool),
+    /// `N`.
+    Nil,
+    /// `I`.
+    Inf,
+    /// `[ ... ]`: the items of an array.
+    Array(Args<'a>),
+}
+
+impl<'a> ArgView<'a> {
+    /// The type tag of this argument (`'['` for arrays).
+    pub fn tag(&self) -> char {
+        match self {
+            ArgView::Int(_) => 'i',
+            ArgView::Float(_) => 'f',
+            ArgView::String(_) => 's',
+            ArgView::Blob(_) => 'b',
+            ArgView::Long(_) => 'h',
+            ArgView::Time(_) => 't',
+            ArgView::Double(_) => 'd',
+            ArgView::Symbol(_) => 'S',
+            ArgView::Char(_) => 'c',
+            ArgView::Color(_) => 'r',
+            ArgView::Midi(_) => 'm',
+            ArgView::Bool(true) => 'T',
+            ArgView::Bool(false) => 'F',
+            ArgView::Nil => 'N',
+            ArgView::Inf => 'I',
+            ArgView::Array(_) => '[',
+        }
+    }
+
+    /// Convert to an [`OscType`], collecting array items.
+    ///
+    /// Recursion is bounded by the depth limit the view was created with.
+    #[cfg(feature = "alloc")]
+    pub fn to_osc_type(&self) -> Result<OscType<'a>> {
+        Ok(match *self {
+            ArgView::Int(v) => OscType::Int(v),
+            ArgView::Float(v) => OscType::Float(v),
+            ArgView::String(s) => OscType::String(s),
+            ArgView::Blob(b) => OscType::Blob(b),
+            ArgView::Long(v) => OscType::Long(v),
+            ArgView::Time(t) => OscType::Time(t),
+            ArgView::Double(v) => OscType::Double(v),
+            ArgView::Symbol(s) => OscType::Symbol(s),
+            ArgView::Char(c) => OscType::Char(c),
+            ArgView::Color(c) => OscType::Color(c),
+            ArgView::Midi(m) => OscType::Midi(m),
+            ArgView::Bool(b) => OscType::Bool(b),
+            ArgView::Nil => OscType::Nil,
+            ArgView::Inf => OscType::Inf,
+            ArgView::Array(ref items) => OscType::Array(
+                items
+                    .clone()
+                    .map(|item| item?.to_osc_type())
+                    .collect::<Result<_>>()?,
+            ),
+        })
+    }
+}
+
+/// Read the data of a single non-array argument with type tag `t` at `*off`.
+///
+/// Returns `Ok(None)` if `t` is not a known type tag.
+pub(crate) fn get_arg<'a>(
+    t: char,
+    bytes: &'a [u8],
+    off: &mut usize,
+) -> Result<Option<ArgView<'a>>> {
+    let start = *off;
+    let arg = match t {
+        'i' => ArgView::Int(get_i32(bytes, off)?),
+        'f' => ArgView::Float(get_f32(bytes, off)?),
+        's' | 'S' => {
+            let (s, new_off) = get_cstr_4(bytes, *off)?;
+            *off = new_off;
+            if t == 's' {
+                ArgView::String(s)
+            } else {
+                ArgView::Symbol(s)
+            }
+        }
+        'b' => {
+            let len = usize::try_from(get_i32(bytes, off)?)
+                .ok()
+                .filter(|&len| len <= bytes.len() - *off)
+                .ok_or(Error::new(ErrorKind::InvalidBlobSize, start))?;
+            let blob = &bytes[*off..*off + len];
+            *off += len;
+            let pad = pad4_len(len);
+            if *off + pad > bytes.len() {
+                return Err(Error::new(ErrorKind::UnexpectedEof, *off));
+            }
+            *off += pad;
+            ArgView::Blob(blob)
+        }
+        'h' => ArgView::Long(get_i64(bytes, off)?),
+        't' => ArgView::Time(TimeTag::from_bits(get_u64(bytes, off)?)),
+        'd' => ArgView::Double(get_f64(bytes, off)?),
+        'c' => {
+            let c = char::from_u32(get_u32(bytes, off)?)
+                .ok_or(Error::new(ErrorKind::InvalidChar, start))?;
+            ArgView::Char(c)
+        }
+        'r' => {
+            let [red, green, blue, alpha] = get_u32(bytes, off)?.to_be_bytes();
+            ArgView::Color(Color::new(red, green, blue, alpha))
+        }
+        'm' => {
+            let [port, status, data1, data2] = get_u32(bytes, off)?.to_be_bytes();
+            ArgView::Midi(MidiMessage::new(port, status, data1, data2))
+        }
+        'T' => ArgView::Bool(true),
+        'F' => ArgView::Bool(false),
+        'N' => ArgView::Nil,
+        'I' => ArgView::Inf,
+        _ => return Ok(None),
+    };
+    Ok(Some(arg))
+}
+
+/// Check that every type tag is known, that array brackets balance and that
+/// arrays are nested at most `max_depth` deep.
+///
+/// `tag_off` is the offset of the first tag after the `,`.
+fn validate_tags(tags: &str, tag_off: usize, max_depth: usize) -> Result<()> {
+    let mut depth = 0usize;
+    for (index, (pos, t)) in tags.char_indices().enumerate() {
+        match t {
+            '[' if depth >= max_depth => {
+                return Err(Error::new(ErrorKind::NestingTooDeep, tag_off + pos).with_arg(index, t))
+            }
+            '[' => depth += 1,
+            ']' if depth == 0 => {
+                return Err(Error::new(ErrorKind::UnbalancedArray, tag_off + pos).with_arg(index, t))
+            }
+            ']' => depth -= 1,
+            'i' | 'f' | 's' | 'b' | 'h' | 't' | 'd' | 'S' | 'c' | 'r' | 'm' | 'T' | 'F' | 'N'
+            | 'I' => {}
+            _ => {
+                return Err(Error::new(ErrorKind::UnknownTypeTag, tag_off + pos).with_arg(index, t))
+            }
+        }
+    }
+    if depth > 0 {
+        // Report the innermost unclosed bracket. All tags are ASCII by now, so
+        // byte positions are tag indices.
+        let mut closed = 0;
+        for (index, t) in tags.bytes().enumerate().rev() {
+            match t {
+                b']' => closed += 1,
+                b'[' if closed == 0 => {
+                    return Err(Error::new(ErrorKind::UnbalancedArray, tag_off + index)
+                        .with_arg(index, '['));
+                }
+                b'[' => closed -= 1,
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A message whose header has been validated and whose arguments are decoded
+/// lazily, straight from the input bytes.
+///
+/// Creating the view checks the address and the type tag string (known tags,
+/// balanced arrays nested at most [`Limits::max_depth`] deep) but not the
+/// argument data; malformed data is reported by the [`Args`] iterator when it
+/// is reached. Nothing is allocated, so views can be used without `alloc`.
+///
+/// Error offsets are relative to the bytes the view was created from.
+///
+/// ```
+/// use osc_codec10::{ArgView, MessageView};
+///
+/// let bytes = b"/synth/1/freq\0\0\0,fs\0\x43\xdc\0\0sine\0\0\0\0";
+/// let view = MessageView::new(bytes).unwrap();
+///
+/// assert_eq!(view.address(), "/synth/1/freq");
+/// assert_eq!(view.type_tags(), "fs");
+/// assert!(matches!(view.args().next(), Some(Ok(ArgView::Float(f))) if f == 440.0));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MessageView<'a> {
+    bytes: &'a [u8],
+    address: &'a str,
+    tags: &'a str,
+    args_start: usize,
+    /// Applied by `to_message`.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    limits: Limits,
+}
+
+impl<'a> MessageView<'a> {
+    /// Validate the header of the message in `bytes` with the default [`Limits`].
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        Self::with_limits(bytes, &Limits::default())
+    }
+
+    /// Validate the header of the message in `bytes`, allowing arrays to nest
+    /// up to `limits.max_depth` deep.
+    ///
+    /// The other limits only apply to [`MessageView::to_message`]: the view
+    /// itself neither allocates nor copies.
+    pub fn with_limits(bytes: &'a [u8], limits: &Limits) -> Result<Self> {
+        let (address, tag_start) = get_cstr_4(bytes, 0)?;
+        if tag_start == bytes.len() {
+            return Err(Error::new(ErrorKind::MissingTypeTags, tag_start));
+        }
+        let (tag, args_start) = get_cstr_4(bytes, tag_start)?;
+        let Some(tags) = tag.strip_prefix(',') else {
+            return Err(Error::new(ErrorKind::MissingTypeTags, tag_start));
+        };
+        validate_tags(tags, tag_start + 1, limits.max_depth)?;
+        Ok(Self {
+            bytes,
+            address,
+            tags,
+            args_start,
+            limits: *limits,
+        })
+    }
+
+    /// The address pattern.
+    pub fn address(&self) -> &'a str {
+        self.address
+    }
+
+    /// The type tags, without the leading `,`.
+    pub fn type_tags(&self) -> &'a str {
+        self.tags
+    }
+
+    /// Iterate over the top-level arguments.
+    pub fn args(&self) -> Args<'a> {
+        Args {
+            bytes: self.bytes,
+            tags: self.tags.as_bytes(),
+            index: 0,
+            end: self.tags.len(),
+            off: self.args_start,
+            failed: false,
+        }
+    }
+
+    /// The bytes the view was created from.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Decode the whole message, enforcing the [`Limits`] the view was
+    /// created with.
+    #[cfg(feature = "alloc")]
+    pub fn to_message(&self) -> Result<Message<'a>> {
+        let options = crate::DecodeOptions::default().with_limits(self.limits);
+        crate::decode_message_with(self.bytes, &options).map(|(msg, _)| msg)
+    }
+}
+
+/// Iterator over the arguments of a [`MessageView`] or of an array in it.
+///
+/// Yields an error and then stops if an argument's data is malformed.
+#[derive(Debug, Clone)]
+pub struct Args<'a> {
+    bytes: &'a [u8],
+    /// The whole type tag string after the `,`; always ASCII.
+    tags: &'a [u8],
+    /// Index in `tags` of the next argument.
+    index: usize,
+    /// Index in `tags` where this level ends.
+    end: usize,
+    /// Offset in `bytes` of the next argument's data.
+    off: usize,
+    failed: bool,
+}
+
+impl<'a> Args<'a> {
+    fn next_arg(&mut self) -> Result<ArgView<'a>> {
+        let index = self.index;
+        let t = self.tags[index] as char;
+        if t != '[' {
+            self.index += 1;
+            let arg = get_arg(t, self.bytes, &mut self.off).map_err(|e| e.with_arg(index, t))?;
+            return Ok(arg.expect("type tags are validated"));
+        }
+
+        // Find the matching `]`, skipping the data of every item on the way.
+        let items_off = self.off;
+        let mut depth = 0;
+        let mut close = index + 1;
+        loop {
+            match self.tags[close] {
+                b'[' => depth += 1,
+                b']' if depth == 0 => break,
+                b']' => depth -= 1,
+                tag => {
+                    get_arg(tag as char, self.bytes, &mut self.off)
+                        .map_err(|e| e.with_arg(close, tag as char))?;
+                }
+            }
+            close += 1;
+        }
+        self.index = close + 1;
+        Ok(ArgView::Array(Args {
+            bytes: self.bytes,
+            tags: self.tags,
+            index: index + 1,
+            end: close,
+            off: items_off,
+            failed: false,
+        }))
+    }
+}
+
+impl<'a> Iterator for Args<'a> {
+    type Item = Result<ArgView<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.index >= self.end {
+            return None;
+        }
+        let arg = self.next_arg();
+        self.failed = arg.is_err();
+        Some(arg)
+    }
+}
+
+/// A bundle whose header has been validated and whose elements are decoded
+/// lazily, straight from the input bytes.
+///
+/// Like [`MessageView`], nothing is allocated and error offsets are relative to
+/// the bytes the view was created from.
+///
+/// ```
+/// use osc_codec10::{BundleView, PacketView};
+///
+/// let bytes = b"#bundle\0\0\0\0\0\0\0\0\x01\0\0\0\x08/a\0\0,\0\0\0";
+/// let view = BundleView::new(bytes).unwrap();
+///
+/// assert_eq!(view.timetag(), 1);
+/// for element in view.elements() {
+///     match element.unwrap() {
+///         PacketView::Message(msg) => assert_eq!(msg.address(), "/a"),
+///         PacketView::Bundle(_) => unreachable!(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BundleView<'a> {
+    bytes: &'a [u8],
+    timetag: TimeTag,
+    limits: Limits,
+}
+
+impl<'a> BundleView<'a> {
+    /// Validate the `#bundle` string and time tag of the bundle in `bytes`.
+    ///
+    /// Messages in the bundle are checked with the default [`Limits`].
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        Self::with_limits(bytes, &Limits::default())
+    }
+
+    /// Validate the `#bundle` string and time tag of the bundle in `bytes`.
+    ///
+    /// Messages in the bundle are checked as by [`MessageView::with_limits`].
+    pub fn with_limits(bytes: &'a [u8], limits: &Limits) -> Result<Self> {
+        let (tag, mut off) = get_cstr_4(bytes, 0)?;
+        if tag != BUNDLE_TAG {
+            return Err(Error::new(ErrorKind::InvalidBundleTag, 0));
+        }
+        let timetag = TimeTag::from_bits(get_u64(bytes, &mut off)?);
+        Ok(Self {
+            bytes,
+            timetag,
+            limits: *limits,
+        })
+    }
+
+    /// The time tag of the bundle.
+    pub fn timetag(&self) -> TimeTag {
+        self.timetag
+    }
+
+    /// Iterate over the elements.
+    pub fn elements(&self) -> Elements<'a> {
+        Elements {
+            bytes: self.bytes,
+            off: BUNDLE_PREFIX.len() + 8,
+            index: 0,
+            limits: self.limits,
+            failed: false,
+        }
+    }
+
+    /// The bytes the view was created from.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+/// Iterator over the elements of a [`BundleView`].
+///
+/// Yields an error and then stops if an element is malformed.
+#[derive(Debug, Clone)]
+pub struct Elements<'a> {
+    bytes: &'a [u8],
+    off: usize,
+    index: usize,
+    limits: Limits,
+    failed: bool,
+}
+
+impl<'a> Elements<'a> {
+    fn next_element(&mut self) -> Result<PacketView<'a>> {
+        let size_off = self.off;
+        let size = get_i32(self.bytes, &mut self.off)?;
+        let size = usize::try_from(size)
+            .ok()
+            .filter(|&size| size <= self.bytes.len() - self.off)
+            .ok_or(Error::new(ErrorKind::InvalidElementSize, size_off))?;
+
+        let start = self.off;
+        self.off += size;
+        let index = self.index;
+        self.index += 1;
+        PacketView::with_limits(&self.bytes[start..self.off], &self.limits)
+            .map_err(|e| e.in_element(index, start))
+    }
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = Result<PacketView<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.off >= self.bytes.len() {
+            return None;
+        }
+        let element = self.next_element();
+        self.failed = element.is_err();
+        Some(element)
+    }
+}
+
+/// A lazily decoded message or bundle.
+#[derive(Debug, Clone, Copy)]
+pub enum PacketView<'a> {
+    /// A message view.
+    Message(MessageView<'a>),
+    /// A bundle view.
+    Bundle(BundleView<'a>),
+}
+
+impl<'a> PacketView<'a> {
+    /// Validate the header of the packet in `bytes`.
+    ///
+    /// As with [`decode_packet`](crate::decode_packet), the packet is a bundle
+    /// if and only if it starts with the `#bundle` string.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        Self::with_limits(bytes, &Limits::default())
+    }
+
+    /// Validate the header of the packet in `bytes` as by
+    /// [`MessageView::with_limits`].
+    pub fn with_limits(bytes: &'a [u8], limits: &Limits) -> Result<Self> {
+        if bytes.starts_with(BUNDLE_PREFIX) {
+            BundleView::with_limits(bytes, limits).map(PacketView::Bundle)
+        } else {
+            MessageView::with_limits(bytes, limits).map(PacketView::Message)
+        }
+    }
+}
+
+/// Encode a message with address `address` and arguments `args` into `buf`
+/// without allocating.
+///
+/// Returns the number of bytes written, or
+/// [`ErrorKind::BufferTooSmall`] if `buf` cannot hold the message. Arrays are
+/// written from their [`Args`], so arguments read from a [`MessageView`] can
+/// be forwarded as they are; an error in their data is returned unchanged.
+///
+/// ```
+/// use osc_codec10::{encode_message_into, ArgView, MessageView};
+///
+/// let mut buf = [0u8; 32];
+/// let len = encode_message_into("/a", &[ArgView::Int(1), ArgView::String("hi")], &mut buf).unwrap();
+/// assert_eq!(&buf[..len], b"/a\0\0,is\0\0\0\0\x01hi\0\0");
+///
+/// let view = MessageView::new(&buf[..len]).unwrap();
+/// assert_eq!(view.type_tags(), "is");
+/// ```
+pub fn encode_message_into(address: &str, args: &[ArgView<'_>], buf: &mut [u8]) -> Result<usize> {
+    let mut sink = SliceSink { buf, len: 0 };
+    put_message(&mut sink, address, args)?;
+    Ok(sink.len)
+}
+
+/// Number of bytes [`encode_message_into`] writes for a message with address
+/// `address` and arguments `args`, computed without allocating.
+///
+/// Fails only if the data of an array read from a [`MessageView`] is
+/// malformed.
+///
+/// ```
+/// use osc_codec10::{encode_message_into, encoded_message_len, ArgView};
+///
+/// let args = [ArgView::Int(1), ArgView::String("hi")];
+/// let mut buf = [0u8; 32];
+/// let len = encode_message_into("/a", &args, &mut buf).unwrap();
+/// assert_eq!(encoded_message_len("/a", &args), Ok(len));
+/// ```
+pub fn encoded_message_len(address: &str, args: &[ArgView<'_>]) -> Result<usize> {
+    let mut len = Counter(0);
+    put_message(&mut len, address, args)?;
+    Ok(len.0)
+}
+
+fn put_message(buf: &mut impl Sink, address: &str, args: &[ArgView<'_>]) -> Result<()> {
+    put_str(buf, address)?;
+    buf.put(b",")?;
+    let mut tags = 0;
+    for arg in args {
+        tags += put_tags(buf, arg)?;
+    }
+    put_nul_padding(buf, 1 + tags)?;
+    args.iter().try_for_each(|arg| put_arg(buf, arg))
+}
+
+/// Writes a bundle into a caller-provided buffer without allocating.
+///
+/// The `#bundle` header is written by [`BundleWriter::new`]; elements are then
+/// appended one at a time, each behind a size prefix that is filled in once
+/// the element is complete, as the allocating encoders do. Nested bundles are
+/// written by the closure passed to [`BundleWriter::bundle`].
+///
+/// Every method fails with [`ErrorKind::BufferTooSmall`] if the buffer cannot
+/// hold what is written; the contents of the buffer are then unspecified.
+///
+/// ```
+/// use osc_codec10::{ArgView, BundleView, BundleWriter, PacketView};
+///
+/// let mut buf = [0u8; 128];
+/// let mut writer = BundleWriter::new(&mut buf, 1).unwrap();
+/// writer.message("/a", &[ArgView::Int(1)]).unwrap();
+/// writer
+///     .bundle(2, |inner| inner.message("/b", &[]))
+///     .unwrap();
+/// let len = writer.finish();
+///
+/// let view = BundleView::new(&buf[..len]).unwrap();
+/// let mut elements = view.elements();
+/// assert!(matches!(elements.next(), Some(Ok(PacketView::Message(m))) if m.address() == "/a"));
+/// assert!(matches!(elements.next(), Some(Ok(PacketView::Bundle(b))) if b.timetag() == 2));
+/// assert!(elements.next().is_none());
+/// ```
+#[derive(Debug)]
+pub struct BundleWriter<'b> {
+    sink: SliceSink<'b>,
+}
+
+impl<'b> BundleWriter<'b> {
+    /// Start a bundle with time tag `timetag` at the beginning of `buf`.
+    pub fn new(buf: &'b mut [u8], timetag: impl Into<TimeTag>) -> Result<Self> {
+        let mut sink = SliceSink { buf, len: 0 };
+        put_bundle_header(&mut sink, timetag.into())?;
+        Ok(Self { sink })
+    }
+
+    /// Append a message element, written as by [`encode_message_into`].
+    pub fn message(&mut self, address: &str, args: &[ArgView<'_>]) -> Result<()> {
+        self.element(|w| put_message(&mut w.sink, address, args))
+    }
+
+    /// Append a nested bundle element with time tag `timetag`, whose elements
+    /// are appended by `elements`.
+    pub fn bundle(
+        &mut self,
+        timetag: impl Into<TimeTag>,
+        elements: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<()> {
+        let timetag = timetag.into();
+        self.element(|w| {
+            put_bundle_header(&mut w.sink, timetag)?;
+            elements(w)
+        })
+    }
+
+    /// Finish the bundle, returning its length in bytes.
+    pub fn finish(self) -> usize {
+        self.sink.len
+    }
+
+    /// Write an element with `put` behind a size prefix that is patched
+    /// afterwards.
+    fn element(&mut self, put: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let size_at = self.sink.position();
+        self.sink.put(&ZEROS)?;
+        put(self)?;
+        let size = self.sink.position() - size_at - 4;
+        self.sink.patch(size_at, (size as i32).to_be_bytes());
+        Ok(())
+    }
+}
+
+fn put_bundle_header(buf: &mut impl Sink, timetag: TimeTag) -> Result<()> {
+    buf.put(BUNDLE_PREFIX)?;
+    put_u64(buf, timetag.to_bits())
+}
+
+/// Write the type tags of `arg`, returning how many were written.
+fn put_tags(buf: &mut impl Sink, arg: &ArgView<'_>) -> Result<usize> {
+    let tag = arg.tag();
+    buf.put(&[tag as u8])?;
+    let ArgView::Array(items) = arg else {
+        return Ok(1);
+    };
+    let mut count = 2;
+    for item in items.clone() {
+        count += put_tags(buf, &item?)?;
+    }
+    buf.put(b"]")?;
+    Ok(count)
+}
+
+fn put_arg(buf: &mut impl Sink, arg: &ArgView<'_>) -> Result<()> {
+    match *arg {
+        ArgView::Int(v) => put_i32(buf, v),
+        ArgView::Float(v) => put_f32(buf, v),
+        ArgView::String(s) | ArgView::Symbol(s) => put_str(buf, s),
+        ArgView::Blob(b) => {
+            put_i32(buf, b.len() as i32)?;
+            buf.put(b)?;
+            buf.put(&ZEROS[..pad4_len(b.len())])
+        }
+        ArgView::Long(v) => put_i64(buf, v),
+        ArgView::Time(t) => put_u64(buf, t.to_bits()),
+        ArgView::Double(v) => put_f64(buf, v),
+        ArgView::Char(c) => put_u32(buf, c as u32),
+        ArgView::Color(c) => buf.put(&[c.red, c.green, c.blue, c.alpha]),
+        ArgView::Midi(m) => buf.put(&[m.port, m.status, m.data1, m.data2]),
+        // Carried by the type tag alone.
+        ArgView::Bool(_) | ArgView::Nil | ArgView::Inf => Ok(()),
+        ArgView::Array(ref items) => {
+            for item in items.clone() {
+                put_arg(buf, &item?)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,230 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{
+    annotate, annotate_with, decode_packet, decode_packet_with, encode_bundle, encode_message,
+    ArgView, DecodeOptions, ErrorKind, Field, Limits,
+};
+use osc_types10::{Bundle, Message, OscType};
+
+fn nested() -> Bundle<'static> {
+    let mut inner = Bundle::empty(1);
+    inner.add_message(Message::new(
+        "/inner",
+        vec![OscType::Blob(&[1, 2, 3]), OscType::Char('x')],
+    ));
+    let mut outer = Bundle::empty(7);
+    outer.add_message(Message::new(
+        "/first",
+        vec![
+            OscType::String("hi"),
+            OscType::Array(vec![OscType::Int(1), OscType::Nil]),
+        ],
+    ));
+    outer.add_bundle(inner);
+    outer
+}
+
+#[test]
+fn covers_every_byte_in_order() {
+    let bytes = encode_bundle(&nested());
+    let annotation = annotate(&bytes);
+    assert!(annotation.is_valid());
+
+    let mut end = 0;
+    for span in annotation.spans() {
+        assert_eq!(span.range.start, end);
+        assert!(!span.range.is_empty());
+        end = span.range.end;
+    }
+    assert_eq!(end, bytes.len());
+
+    let fields: Vec<String> = annotation
+        .spans()
+        .iter()
+        .filter(|span| !matches!(span.field, Field::Padding))
+        .map(|span| format!("{} {}", span.depth, span.field))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            "0 bundle tag",
+            "0 timetag 7",
+            "0 element size 24",
+            "1 address \"/first\"",
+            "1 type tags \",s[iN]\"",
+            "1 argument 0 's': \"hi\"",
+            "1 argument 2 'i': 1",
+            "0 element size 44",
+            "1 bundle tag",
+            "1 timetag immediately",
+            "1 element size 24",
+            "2 address \"/inner\"",
+            "2 type tags \",bc\"",
+            "2 argument 0 'b': 3 bytes",
+            "2 argument 1 'c': 'x'",
+        ]
+    );
+
+    // The blob span holds the size prefix and the data, not the padding.
+    let blob = annotation
+        .spans()
+        .iter()
+        .find(|span| {
+            matches!(
+                span.field,
+                Field::Argument {
+                    value: ArgView::Blob(_),
+                    ..
+                }
+            )
+        })
+        .unwrap();
+    assert_eq!(&bytes[blob.range.clone()], &[0, 0, 0, 3, 1, 2, 3]);
+}
+
+#[test]
+fn first_error_matches_the_decoder() {
+    let valid = encode_bundle(&nested());
+    let message = encode_message(&Message::new("/a", vec![OscType::Int(1), OscType::Long(2)]));
+
+    let mut cases: Vec<Vec<u8>> = vec![
+        b"/a\0\0".to_vec(),
+        b"/a\0\0i\0\0\0".to_vec(),
+        b"/a\0\0,i]\0\0\0\0\0".to_vec(),
+        b"/a\0\0,[i\0\0\0\0\0".to_vec(),
+        b"/a\0\0,b\0\0\xff\xff\xff\xff".to_vec(),
+        b"/\xff\0\0,\0\0\0".to_vec(),
+        b"/abc".to_vec(),
+        b"#bundle\0\0\0".to_vec(),
+        [&message[..], &[0; 4]].concat(),
+        message[..message.len() - 2].to_vec(),
+    ];
+    for cut in [20, 30, 50, valid.len() - 3] {
+        cases.push(valid[..cut].to_vec());
+    }
+    let mut bad_size = valid.clone();
+    bad_size[16..20].copy_from_slice(&27i32.to_be_bytes());
+    cases.push(bad_size);
+    let mut bad_char = valid.clone();
+    let len = bad_char.len();
+    bad_char[len - 4..].copy_from_slice(&0xd800u32.to_be_bytes());
+    cases.push(bad_char);
+
+    for bytes in &cases {
+        let expected = decode_packet(bytes).unwrap_err();
+        let annotation = annotate(bytes);
+        assert_eq!(annotation.errors().next(), Some(&expected), "{bytes:?}");
+        assert_eq!(
+            annotation.spans().last().unwrap().range.end,
+            bytes.len(),
+            "{bytes:?}"
+        );
+    }
+
+    let options = DecodeOptions::new().with_limits(Limits {
+        max_string_size: 4,
+        ..Limits::default()
+    });
+    let expected = decode_packet_with(&valid, &options).unwrap_err();
+    assert_eq!(expected.kind(), ErrorKind::StringTooLarge);
+    assert_eq!(
+        annotate_with(&valid, &options).errors().next(),
+        Some(&expected)
+    );
+}
+
+#[test]
+fn continues_past_broken_elements() {
+    let mut bundle = Bundle::empty(1);
+    bundle.add_message(Message::new("/bad", vec![OscType::Char('x')]));
+    bundle.add_message(Message::new(
+        "/bad",
+        vec![OscType::String("é"), OscType::Int(5)],
+    ));
+    bundle.add_message(Message::new("/bad", vec![OscType::Int(1)]));
+    bundle.add_message(Message::new("/good", vec![OscType::Int(2)]));
+    let mut bytes = encode_bundle(&bundle);
+
+    // Element 0: lone surrogate as the char.
+    bytes[32..36].copy_from_slice(&0xd800u32.to_be_bytes());
+    // Element 1: the string is no longer UTF-8.
+    bytes[52] = 0xff;
+    // Element 2: unknown type tag.
+    bytes[73] = b'x';
+
+    let annotation = annotate(&bytes);
+    let errors: Vec<_> = annotation
+        .errors()
+        .map(|err| (err.kind(), err.offset(), err.path().to_vec()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (ErrorKind::InvalidChar, 32, vec![0]),
+            (ErrorKind::InvalidUtf8, 52, vec![1]),
+            (ErrorKind::UnknownTypeTag, 73, vec![2]),
+        ]
+    );
+
+    // The arguments after the broken string and the last element still decode.
+    let ints: Vec<_> = annotation
+        .spans()
+        .iter()
+        .filter_map(|span| match span.field {
+            Field::Argument {
+                value: ArgView::Int(v),
+                ..
+            } => Some(v),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(ints, [5, 2]);
+    assert_eq!(annotation.spans().last().unwrap().range.end, bytes.len());
+}
+
+#[test]
+fn follows_bundle_fallback() {
+    let bundle = Bundle::with_messages(1, vec![Message::new("#bundle", vec![])]);
+    let bytes = encode_bundle(&bundle);
+
+    let strict = annotate(&bytes);
+    assert_eq!(
+        strict.errors().next(),
+        Some(&decode_packet(&bytes).unwrap_err())
+    );
+
+    let lenient = annotate_with(&bytes, &DecodeOptions::new().with_bundle_fallback(true));
+    assert!(lenient.is_valid());
+    assert!(lenient
+        .spans()
+        .iter()
+        .any(|span| matches!(span.field, Field::Address("#bundle"))));
+}
+
+#[test]
+fn renders_a_hexdump() {
+    let bundle = Bundle::with_messages(
+        2,
+        vec![Message::new("/b", vec![OscType::Blob(&[0x41; 17])])],
+    );
+    let mut bytes = encode_bundle(&bundle);
+    // The blob claims 64 bytes.
+    bytes[31] = 0x40;
+
+    let dump = annotate(&bytes).to_string();
+    let lines: Vec<&str> = dump.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "00000000  23 62 75 6e 64 6c 65 00                          |#bundle.        |  bundle tag",
+            "00000008  00 00 00 00 00 00 00 02                          |........        |  timetag 2",
+            "00000010  00 00 00 20                                      |...             |  element size 32",
+            "00000014  2f 62                                            |/b              |    address \"/b\"",
+            "00000016  00 00                                            |..              |    padding",
+            "00000018  2c 62                                            |,b              |    type tags \",b\"",
+            "0000001a  00 00                                            |..              |    padding",
+            "0000001c  00 00 00 40 41 41 41 41 41 41 41 41 41 41 41 41  |...@AAAAAAAAAAAA|    error: invalid blob size at byte 28 (argument 0, type tag 'b') in bundle element 0",
+            "0000002c  41 41 41 41 41 00 00 00                          |AAAAA...        |",
+        ]
+    );
+}
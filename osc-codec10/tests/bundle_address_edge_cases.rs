@@ -1,9 +1,52 @@
-use osc_codec10::{decode_bundle, decode_message, encode_bundle, encode_message};
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{
+    decode_bundle, decode_bundle_with, decode_message, encode_bundle, encode_message,
+    DecodeOptions, ErrorKind,
+};
 use osc_types10::{Bundle, Message, OscPacket, OscType};
 
+fn fallback() -> DecodeOptions {
+    DecodeOptions::new().with_bundle_fallback(true)
+}
+
+#[test]
+fn bundle_address_message_is_a_bundle_by_default() {
+    // Per the spec an element starting with "#bundle\0" is a bundle, so a
+    // message addressed to "#bundle" inside a bundle is malformed.
+    let msg = Message::new("#bundle", vec![OscType::String("not a bundle")]);
+    let encoded = encode_bundle(&Bundle::with_messages(42, vec![msg]));
+
+    let err = decode_bundle(&encoded).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidElementSize);
+    assert_eq!(err.path(), &[0]);
+}
+
+#[test]
+fn corrupt_nested_bundle_is_not_decoded_as_message() {
+    let inner = Bundle::with_messages(200, vec![Message::new("/m", vec![OscType::Int(1)])]);
+    let mut outer = Bundle::empty(100);
+    outer.add_bundle(inner);
+
+    let mut encoded = encode_bundle(&outer);
+    // Make the inner message's type tag string invalid.
+    let len = encoded.len();
+    encoded[len - 8] = b'x';
+
+    let err = decode_bundle(&encoded).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingTypeTags);
+    assert_eq!(err.path(), &[0, 0]);
+    assert_eq!(err.offset(), len - 8);
+
+    // The fallback reports the bundle error too, since the element is not a
+    // valid message either.
+    assert_eq!(decode_bundle_with(&encoded, &fallback()), Err(err));
+}
+
 #[test]
 fn bundle_vs_message_with_bundle_address() {
-    // Test that messages with "#bundle" address are not misclassified as bundles
+    // With the fallback enabled, messages with "#bundle" address are not
+    // misclassified as bundles
 
     // Create a message with "#bundle" as the address
     let bundle_address_msg = Message::new("#bundle", vec![OscType::String("not a bundle")]);
@@ -13,7 +56,7 @@ fn bundle_vs_message_with_bundle_address() {
 
     // Encode and decode the bundle
     let encoded = encode_bundle(&bundle);
-    let (decoded_bundle, used) = decode_bundle(&encoded).unwrap();
+    let (decoded_bundle, used) = decode_bundle_with(&encoded, &fallback()).unwrap();
 
     assert_eq!(used, encoded.len());
     assert_eq!(decoded_bundle.timetag, 42);
@@ -61,12 +104,13 @@ fn message_with_bundle_prefix_address() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn actual_nested_bundle_still_works() {
     // Ensure that real nested bundles still work correctly
     let inner_msg = Message::new("/real/message", vec![OscType::Float(3.14)]);
     let inner_bundle = Bundle::with_messages(200, vec![inner_msg]);
 
-    let outer_msg = Message::new("#bundle", vec![OscType::String("confusing message")]);
+    let outer_msg = Message::new("/outer", vec![OscType::String("plain message")]);
     let mut outer_bundle = Bundle::empty(100);
     outer_bundle.add_message(outer_msg);
     outer_bundle.add_bundle(inner_bundle.clone());
@@ -77,6 +121,38 @@ fn actual_nested_bundle_still_works() {
     assert_eq!(decoded_bundle.timetag, 100);
     assert_eq!(decoded_bundle.packets.len(), 2);
 
+    if let OscPacket::Message(ref msg) = decoded_bundle.packets[0] {
+        assert_eq!(msg.address, "/outer");
+    } else {
+        panic!("Expected message at index 0");
+    }
+
+    // Second should be the actual nested bundle
+    if let OscPacket::Bundle(ref bundle) = decoded_bundle.packets[1] {
+        assert_eq!(bundle, &inner_bundle);
+    } else {
+        panic!("Expected bundle at index 1");
+    }
+}
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn actual_nested_bundle_still_works_with_fallback() {
+    // The fallback must not turn real nested bundles into messages.
+    let inner_msg = Message::new("/real/message", vec![OscType::Float(3.14)]);
+    let inner_bundle = Bundle::with_messages(200, vec![inner_msg]);
+
+    let outer_msg = Message::new("#bundle", vec![OscType::String("confusing message")]);
+    let mut outer_bundle = Bundle::empty(100);
+    outer_bundle.add_message(outer_msg);
+    outer_bundle.add_bundle(inner_bundle.clone());
+
+    let encoded = encode_bundle(&outer_bundle);
+    let (decoded_bundle, _) = decode_bundle_with(&encoded, &fallback()).unwrap();
+
+    assert_eq!(decoded_bundle.timetag, 100);
+    assert_eq!(decoded_bundle.packets.len(), 2);
+
     // First should be the confusing message
     if let OscPacket::Message(ref msg) = decoded_bundle.packets[0] {
         assert_eq!(msg.address, "#bundle");
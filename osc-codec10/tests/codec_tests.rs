@@ -1,5 +1,7 @@
-use osc_codec10::{decode_bundle, decode_message, encode_bundle, encode_message};
-use osc_types10::{Bundle, Message, OscType};
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{decode_bundle, decode_message, encode_bundle, encode_message, ErrorKind};
+use osc_types10::{Bundle, Color, Message, MidiMessage, OscType, TimeTag};
 
 #[test]
 fn roundtrip_message_basic() {
@@ -41,3 +43,94 @@ fn roundtrip_bundle_basic() {
     assert_eq!(b2.timetag, 1);
     assert_eq!(b2.packets.len(), 1);
 }
+
+#[test]
+fn roundtrip_optional_types() {
+    let msg = Message::new(
+        "/optional",
+        vec![
+            OscType::Long(-1_234_567_890_123),
+            OscType::Time(TimeTag::new(0x0102_0304, 0x0506_0708)),
+            OscType::Double(-0.125),
+            OscType::Symbol("sym"),
+            OscType::Char('x'),
+            OscType::Color(Color::new(0x10, 0x20, 0x30, 0xff)),
+            OscType::Midi(MidiMessage::new(1, 0x90, 60, 127)),
+            OscType::Bool(true),
+            OscType::Bool(false),
+            OscType::Nil,
+            OscType::Inf,
+            OscType::Int(7),
+        ],
+    );
+    let bytes = encode_message(&msg);
+    let (m2, used) = decode_message(&bytes).unwrap();
+    assert_eq!(used, bytes.len());
+    assert_eq!(m2, msg);
+}
+
+#[test]
+fn optional_types_wire_layout() {
+    let msg = Message::new(
+        "/a",
+        vec![
+            OscType::Bool(true),
+            OscType::Long(1),
+            OscType::Nil,
+            OscType::Color(Color::new(1, 2, 3, 4)),
+        ],
+    );
+    let bytes = encode_message(&msg);
+    let expected: &[u8] = &[
+        b'/', b'a', 0, 0, // address
+        b',', b'T', b'h', b'N', b'r', 0, 0, 0, // type tags
+        0, 0, 0, 0, 0, 0, 0, 1, // h
+        1, 2, 3, 4, // r
+    ];
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn decode_rejects_invalid_char() {
+    let bytes: &[u8] = &[b'/', b'c', 0, 0, b',', b'c', 0, 0, 0, 0, 0xd8, 0x00];
+    let err = decode_message(bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.offset(), 8);
+    assert_eq!((err.arg_index(), err.type_tag()), (Some(0), Some('c')));
+}
+
+#[test]
+fn roundtrip_nested_arrays() {
+    let msg = Message::new(
+        "/lights",
+        vec![
+            OscType::Int(1),
+            OscType::Array(vec![
+                OscType::Float(0.5),
+                OscType::Array(vec![OscType::String("deep"), OscType::Bool(true)]),
+                OscType::Array(vec![]),
+            ]),
+            OscType::String("tail"),
+        ],
+    );
+    let bytes = encode_message(&msg);
+    // ",i[f[sT][]]s" + NUL, padded to 16 bytes
+    assert_eq!(&bytes[8..24], b",i[f[sT][]]s\0\0\0\0");
+    let (m2, used) = decode_message(&bytes).unwrap();
+    assert_eq!(used, bytes.len());
+    assert_eq!(m2, msg);
+}
+
+#[test]
+fn decode_rejects_unbalanced_array_tags() {
+    let unclosed: &[u8] = &[b'/', b'a', 0, 0, b',', b'[', b'i', 0, 0, 0, 0, 1];
+    let err = decode_message(unclosed).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnbalancedArray);
+    assert_eq!(err.offset(), 5);
+
+    let unopened: &[u8] = &[b'/', b'a', 0, 0, b',', b'i', b']', 0, 0, 0, 0, 1];
+    let err = decode_message(unopened).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnbalancedArray);
+    assert_eq!(err.offset(), 6);
+    assert_eq!(err.arg_index(), Some(1));
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{
+    annotate_with, decode_bundle_with, decode_message, decode_message_with, decode_packet,
+    decode_packet_with, encode_bundle, encode_message, DecodeMode, DecodeOptions, ErrorKind, Field,
+};
+use osc_types10::{Bundle, Message, OscPacket, OscType};
+
+fn options(mode: DecodeMode) -> DecodeOptions {
+    DecodeOptions::new().with_mode(mode)
+}
+
+#[test]
+fn strict_rejects_non_zero_padding() {
+    let msg = Message::new(
+        "/a",
+        vec![
+            OscType::String("hello"),
+            OscType::Blob(&[1, 2, 3]),
+            OscType::Int(4),
+        ],
+    );
+    let bytes = encode_message(&msg);
+    let strict = options(DecodeMode::Strict);
+    assert_eq!(decode_message_with(&bytes, &strict).unwrap().0, msg);
+
+    // Padding after the address, the type tags, "hello" and the blob data.
+    for (at, arg) in [(3, None), (10, None), (19, Some(0)), (27, Some(1))] {
+        let mut bad = bytes.clone();
+        bad[at] = b'x';
+        let err = decode_message_with(&bad, &strict).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NonZeroPadding);
+        assert_eq!(err.offset(), at);
+        assert_eq!(err.arg_index(), arg);
+        assert_eq!(annotate_with(&bad, &strict).errors().next(), Some(&err));
+
+        // The other modes skip padding without looking at it.
+        assert_eq!(decode_message(&bad).unwrap().0, msg);
+        let lenient = options(DecodeMode::Lenient);
+        assert_eq!(decode_message_with(&bad, &lenient).unwrap().0, msg);
+    }
+}
+
+#[test]
+fn strict_rejects_non_ascii_addresses_and_trailing_bytes() {
+    let strict = options(DecodeMode::Strict);
+
+    let bytes = encode_message(&Message::new("/café", vec![]));
+    assert!(decode_message(&bytes).is_ok());
+    let err = decode_message_with(&bytes, &strict).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NonAsciiAddress);
+    assert_eq!(err.offset(), 4);
+    assert_eq!(annotate_with(&bytes, &strict).errors().next(), Some(&err));
+
+    let mut bytes = encode_message(&Message::new("/a", vec![OscType::Int(1)]));
+    let len = bytes.len();
+    bytes.extend_from_slice(&[0; 4]);
+    assert_eq!(decode_message(&bytes).unwrap().1, len);
+    let err = decode_message_with(&bytes, &strict).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TrailingBytes);
+    assert_eq!(err.offset(), len);
+}
+
+#[test]
+fn lenient_exposes_untyped_payloads() {
+    let lenient = options(DecodeMode::Lenient);
+
+    let bytes = b"/a\0\0\0\0\0\x01\x3f\x80\0\0";
+    assert_eq!(
+        decode_message(bytes).unwrap_err().kind(),
+        ErrorKind::MissingTypeTags
+    );
+    let (msg, used) = decode_message_with(bytes, &lenient).unwrap();
+    assert_eq!(msg.address, "/a");
+    assert_eq!(msg.args, [OscType::Blob(&bytes[4..])]);
+    assert_eq!(used, bytes.len());
+
+    let (msg, _) = decode_message_with(b"/a\0\0", &lenient).unwrap();
+    assert!(msg.args.is_empty());
+
+    let annotation = annotate_with(bytes, &lenient);
+    assert!(annotation.is_valid());
+    assert!(matches!(
+        annotation.spans().last().unwrap().field,
+        Field::Payload(payload) if payload == &bytes[4..]
+    ));
+}
+
+#[test]
+fn lenient_ignores_trailing_bytes() {
+    let lenient = options(DecodeMode::Lenient);
+    let msg = Message::new("/a", vec![OscType::Int(1)]);
+
+    let mut bytes = encode_message(&msg);
+    bytes.extend_from_slice(b"junk");
+    assert_eq!(
+        decode_packet(&bytes).unwrap_err().kind(),
+        ErrorKind::TrailingBytes
+    );
+    assert_eq!(
+        decode_packet_with(&bytes, &lenient).unwrap(),
+        OscPacket::Message(msg.clone())
+    );
+
+    // Grow the only element of a bundle by four bytes of junk.
+    let mut bytes = encode_bundle(&Bundle::with_messages(1, vec![msg.clone()]));
+    bytes.extend_from_slice(b"junk");
+    let size = i32::from_be_bytes(bytes[16..20].try_into().unwrap());
+    bytes[16..20].copy_from_slice(&(size + 4).to_be_bytes());
+    assert_eq!(
+        decode_packet(&bytes).unwrap_err().kind(),
+        ErrorKind::InvalidElementSize
+    );
+    let (bundle, _) = decode_bundle_with(&bytes, &lenient).unwrap();
+    assert_eq!(bundle.packets, [OscPacket::Message(msg)]);
+
+    let annotation = annotate_with(&bytes, &lenient);
+    assert!(annotation.is_valid());
+    let last = annotation.spans().last().unwrap();
+    assert!(matches!(last.field, Field::Ignored));
+    assert_eq!(&bytes[last.range.clone()], b"junk");
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{
+    encode_bundle, encode_into, encode_into_vec, encode_message, encode_packet, encoded_len,
+    ErrorKind,
+};
+use osc_types10::{Bundle, Color, Message, OscPacket, OscPacketBuf, OscType};
+
+fn packets() -> Vec<OscPacket<'static>> {
+    let msg = Message::new(
+        "/sensor/1",
+        vec![
+            OscType::Float(0.25),
+            OscType::String("abc"),
+            OscType::Blob(&[1, 2, 3, 4, 5]),
+            OscType::Array(vec![OscType::Int(1), OscType::Bool(true)]),
+            OscType::Color(Color::new(1, 2, 3, 4)),
+        ],
+    );
+    let mut bundle = Bundle::with_messages(7, vec![msg.clone(), Message::new("/e", vec![])]);
+    bundle.add_bundle(Bundle::with_messages(8, vec![msg.clone()]));
+    vec![
+        OscPacket::Message(msg),
+        OscPacket::Message(Message::new("/", vec![])),
+        OscPacket::Bundle(bundle),
+        OscPacket::Bundle(Bundle::empty(1)),
+    ]
+}
+
+#[test]
+fn encode_into_matches_encode_packet() {
+    for packet in packets() {
+        let expected = encode_packet(&packet);
+        assert_eq!(encoded_len(&packet), expected.len());
+
+        let mut buf = [0xffu8; 512];
+        let n = encode_into(&packet, &mut buf).unwrap();
+        assert_eq!(&buf[..n], &expected[..]);
+
+        let mut exact = vec![0; expected.len()];
+        assert_eq!(encode_into(&packet, &mut exact), Ok(expected.len()));
+    }
+}
+
+#[test]
+fn encode_into_reports_buffer_too_small() {
+    for packet in packets() {
+        let len = encoded_len(&packet);
+        for short in [0, 3, len / 2, len - 1] {
+            let mut buf = vec![0; short];
+            let err = encode_into(&packet, &mut buf).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::BufferTooSmall);
+            assert!(err.offset() <= short);
+        }
+    }
+}
+
+#[test]
+fn encode_into_vec_appends() {
+    let packets = packets();
+    let mut buf = b"prefix".to_vec();
+    let mut expected = buf.clone();
+    for packet in &packets {
+        let n = encode_into_vec(packet, &mut buf);
+        let encoded = encode_packet(packet);
+        assert_eq!(n, encoded.len());
+        expected.extend_from_slice(&encoded);
+    }
+    assert_eq!(buf, expected);
+
+    // Reusing a cleared buffer does not reallocate.
+    buf.clear();
+    let capacity = buf.capacity();
+    encode_into_vec(&packets[2], &mut buf);
+    assert_eq!(buf.capacity(), capacity);
+}
+
+#[test]
+fn owned_packets_encode_like_borrowed_ones() {
+    for packet in packets() {
+        let expected = encode_packet(&packet);
+        let owned = OscPacketBuf::from(&packet);
+        assert_eq!(encode_packet(&owned), expected);
+        assert_eq!(encoded_len(&owned), expected.len());
+
+        let mut buf = [0u8; 512];
+        let n = encode_into(&owned, &mut buf).unwrap();
+        assert_eq!(&buf[..n], &expected[..]);
+        let err = encode_into(&owned, &mut buf[..n - 1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::BufferTooSmall);
+
+        let mut vec = b"prefix".to_vec();
+        assert_eq!(encode_into_vec(&owned, &mut vec), n);
+        assert_eq!(&vec[6..], &expected[..]);
+
+        match (&packet, &owned) {
+            (OscPacket::Message(msg), OscPacketBuf::Message(buf)) => {
+                assert_eq!(encode_packet(buf), encode_message(msg));
+                assert_eq!(encode_packet(msg), expected);
+                assert_eq!(encoded_len(buf), expected.len());
+            }
+            (OscPacket::Bundle(bundle), OscPacketBuf::Bundle(buf)) => {
+                assert_eq!(encode_packet(buf), encode_bundle(bundle));
+                assert_eq!(encode_packet(bundle), expected);
+                assert_eq!(encoded_len(buf), expected.len());
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{decode_bundle, decode_message, encode_bundle, encode_message, ErrorKind};
+use osc_types10::{Bundle, Message, OscType};
+
+#[test]
+fn reports_offset_and_argument_of_truncated_data() {
+    let msg = Message::new("/a", vec![OscType::Int(1), OscType::String("hello")]);
+    let bytes = encode_message(&msg);
+
+    // Cut into the middle of the string argument, which starts at byte 12.
+    let err = decode_message(&bytes[..14]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnterminatedString);
+    assert_eq!(err.offset(), 12);
+    assert_eq!(err.arg_index(), Some(1));
+    assert_eq!(err.type_tag(), Some('s'));
+    assert!(err.path().is_empty());
+
+    let err = decode_message(&bytes[..10]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(err.offset(), 8);
+    assert_eq!(err.type_tag(), Some('i'));
+}
+
+#[test]
+fn reports_header_errors_without_argument() {
+    let err = decode_message(b"/a\0\0i\0\0\0").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingTypeTags);
+    assert_eq!(err.offset(), 4);
+    assert_eq!(err.arg_index(), None);
+
+    let err = decode_message(b"/a\0\0").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingTypeTags);
+
+    let err = decode_message(b"/\xff\0\0,\0\0\0").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+    assert_eq!(err.offset(), 0);
+
+    let err = decode_bundle(b"#bundlx\0\0\0\0\0\0\0\0\0").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidBundleTag);
+}
+
+#[test]
+fn reports_path_and_absolute_offset_inside_nested_bundles() {
+    let inner = Bundle::with_messages(1, vec![Message::new("/inner", vec![OscType::Char('x')])]);
+    let mut outer = Bundle::empty(2);
+    outer.add_message(Message::new("/first", vec![]));
+    outer.add_bundle(inner);
+
+    let mut bytes = encode_bundle(&outer);
+    // The char argument is the last four bytes; make it a lone surrogate.
+    let len = bytes.len();
+    bytes[len - 4..].copy_from_slice(&0xd800u32.to_be_bytes());
+
+    let err = decode_bundle(&bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.offset(), len - 4);
+    assert_eq!(err.path(), &[1, 0]);
+    assert_eq!(err.depth(), 2);
+    assert_eq!(err.type_tag(), Some('c'));
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "invalid character argument at byte {} (argument 0, type tag 'c') in bundle element 1/0",
+            len - 4
+        )
+    );
+}
+
+#[test]
+fn rejects_bad_element_sizes() {
+    let bundle = Bundle::with_messages(1, vec![Message::new("/a", vec![])]);
+    let mut bytes = encode_bundle(&bundle);
+
+    bytes[16..20].copy_from_slice(&(-4i32).to_be_bytes());
+    let err = decode_bundle(&bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidElementSize);
+    assert_eq!(err.offset(), 16);
+
+    bytes[16..20].copy_from_slice(&64i32.to_be_bytes());
+    let err = decode_bundle(&bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidElementSize);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn implements_std_error() {
+    let err = decode_message(b"/a").unwrap_err();
+    let boxed: Box<dyn std::error::Error> = Box::new(err);
+    assert_eq!(boxed.to_string(), "string is not NUL-terminated at byte 0");
+}
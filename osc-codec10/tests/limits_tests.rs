@@ -0,0 +1,132 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{
+    decode_bundle, decode_message, decode_message_with, decode_packet, decode_packet_with,
+    encode_bundle, encode_message, DecodeOptions, ErrorKind, Limits, OwnedPacket,
+};
+use osc_types10::{Bundle, Message, OscType};
+
+fn options(limits: Limits) -> DecodeOptions {
+    DecodeOptions::new().with_limits(limits)
+}
+
+/// A message wrapped in `levels` bundles, built by hand so the test does not
+/// depend on the encoder recursing.
+fn nested(levels: usize) -> Vec<u8> {
+    let mut bytes = encode_message(&Message::new("/deep", vec![]));
+    for _ in 0..levels {
+        let mut outer = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+        outer.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+        outer.extend_from_slice(&bytes);
+        bytes = outer;
+    }
+    bytes
+}
+
+#[test]
+fn deep_bundle_nesting_is_rejected_by_default() {
+    let bytes = nested(2000);
+    let err = decode_packet(&bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NestingTooDeep);
+    assert_eq!(err.depth(), Limits::default().max_depth);
+
+    assert!(decode_packet(&nested(Limits::default().max_depth)).is_ok());
+}
+
+#[test]
+fn depth_limit_counts_bundles_and_arrays() {
+    let limits = Limits {
+        max_depth: 2,
+        ..Limits::default()
+    };
+    assert!(decode_packet_with(&nested(2), &options(limits)).is_ok());
+    let err = decode_packet_with(&nested(3), &options(limits)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NestingTooDeep);
+    assert_eq!(err.path(), &[0, 0]);
+
+    let arrays = Message::new(
+        "/a",
+        vec![OscType::Array(vec![OscType::Array(vec![OscType::Array(
+            vec![],
+        )])])],
+    );
+    let bytes = encode_message(&arrays);
+    let err = decode_message_with(&bytes, &options(limits)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NestingTooDeep);
+    assert_eq!(err.arg_index(), Some(2));
+}
+
+#[test]
+fn negative_blob_size_is_rejected() {
+    let mut bytes = encode_message(&Message::new("/b", vec![OscType::Blob(&[1, 2, 3, 4])]));
+    bytes[8..12].copy_from_slice(&(-8i32).to_be_bytes());
+
+    let err = decode_message(&bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidBlobSize);
+    assert_eq!(err.offset(), 8);
+    assert_eq!(err.type_tag(), Some('b'));
+}
+
+#[test]
+fn size_and_count_limits() {
+    let limits = Limits {
+        max_args: 2,
+        max_blob_size: 4,
+        max_string_size: 3,
+        max_elements: 2,
+        ..Limits::default()
+    };
+    let decode =
+        |msg: &Message<'_>| decode_message_with(&encode_message(msg), &options(limits)).map(drop);
+
+    assert!(decode(&Message::new(
+        "/ab",
+        vec![OscType::Blob(&[0; 4]), OscType::Nil]
+    ))
+    .is_ok());
+
+    let err = decode(&Message::new("/a", vec![OscType::Nil; 3])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TooManyArguments);
+
+    let err = decode(&Message::new("/a", vec![OscType::Blob(&[0; 5])])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BlobTooLarge);
+
+    let err = decode(&Message::new("/abc", vec![])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::StringTooLarge);
+    assert_eq!(err.offset(), 0);
+
+    let err = decode(&Message::new("/a", vec![OscType::String("long")])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::StringTooLarge);
+    assert_eq!(err.arg_index(), Some(0));
+
+    // Elements are counted over all nesting levels.
+    let mut outer = Bundle::with_messages(1, vec![Message::new("/a", vec![])]);
+    outer.add_bundle(Bundle::with_messages(1, vec![Message::new("/b", vec![])]));
+    let bytes = encode_bundle(&outer);
+    let err = decode_packet_with(&bytes, &options(limits)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TooManyElements);
+    assert_eq!(err.path(), &[1]);
+    assert!(decode_bundle(&bytes).is_ok());
+}
+
+#[test]
+fn unlimited_and_owned_packets() {
+    let bytes = nested(100);
+    let unlimited = options(Limits::UNLIMITED);
+    assert!(decode_packet_with(&bytes, &unlimited).is_ok());
+
+    let owned = OwnedPacket::from_vec_with(bytes.clone(), &unlimited).unwrap();
+    assert_eq!(owned.clone(), owned);
+    assert_eq!(
+        OwnedPacket::from_vec_with(
+            bytes,
+            &options(Limits {
+                max_depth: 99,
+                ..Limits::UNLIMITED
+            })
+        )
+        .unwrap_err()
+        .kind(),
+        ErrorKind::NestingTooDeep
+    );
+}
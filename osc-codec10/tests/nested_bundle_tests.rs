@@ -1,3 +1,5 @@
+#![cfg(feature = "alloc")]
+
 use osc_codec10::{decode_bundle, encode_bundle};
 use osc_types10::{Bundle, Message, OscPacket, OscType};
 
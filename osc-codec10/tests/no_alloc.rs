@@ -0,0 +1,139 @@
+//! The API that is available without `alloc`; these tests also run in
+//! `--no-default-features` builds.
+
+use osc_codec10::{
+    encode_message_into, encoded_message_len, ArgView, BundleView, BundleWriter, ErrorKind,
+    MessageView, PacketView,
+};
+
+const MESSAGE: &[u8] = b"/a\0\0,i[sf]\0\0\0\0\0\x07hi\0\0\x3f\x80\0\0";
+
+#[test]
+fn views_decode_without_alloc() {
+    let view = MessageView::new(MESSAGE).unwrap();
+    assert_eq!(view.address(), "/a");
+    assert_eq!(view.type_tags(), "i[sf]");
+
+    let mut args = view.args();
+    assert!(matches!(args.next(), Some(Ok(ArgView::Int(7)))));
+    let Some(Ok(ArgView::Array(mut items))) = args.next() else {
+        panic!("expected an array");
+    };
+    assert!(matches!(items.next(), Some(Ok(ArgView::String("hi")))));
+    assert!(matches!(items.next(), Some(Ok(ArgView::Float(f))) if f == 1.0));
+    assert!(items.next().is_none());
+    assert!(args.next().is_none());
+}
+
+#[test]
+fn bundle_views_without_alloc() {
+    let mut bundle = [0u8; 64];
+    bundle[..16].copy_from_slice(b"#bundle\0\0\0\0\0\0\0\0\x02");
+    bundle[16..20].copy_from_slice(&(MESSAGE.len() as i32).to_be_bytes());
+    bundle[20..20 + MESSAGE.len()].copy_from_slice(MESSAGE);
+    let bytes = &bundle[..20 + MESSAGE.len()];
+
+    let Ok(PacketView::Bundle(view)) = PacketView::new(bytes) else {
+        panic!("expected a bundle");
+    };
+    assert_eq!(view.timetag(), 2);
+    let mut elements = view.elements();
+    assert!(matches!(
+        elements.next(),
+        Some(Ok(PacketView::Message(msg))) if msg.as_bytes() == MESSAGE
+    ));
+    assert!(elements.next().is_none());
+    assert_eq!(
+        BundleView::new(MESSAGE).unwrap_err().kind(),
+        ErrorKind::InvalidBundleTag
+    );
+}
+
+#[test]
+fn encode_message_into_round_trips_views() {
+    let view = MessageView::new(MESSAGE).unwrap();
+    let mut args = [ArgView::Nil, ArgView::Nil];
+    for (slot, arg) in args.iter_mut().zip(view.args()) {
+        *slot = arg.unwrap();
+    }
+
+    let mut buf = [0u8; 64];
+    let len = encode_message_into(view.address(), &args, &mut buf).unwrap();
+    assert_eq!(&buf[..len], MESSAGE);
+
+    let err = encode_message_into("/a", &args, &mut buf[..len - 1]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BufferTooSmall);
+}
+
+#[test]
+fn encode_message_into_pads_every_type() {
+    let mut buf = [0u8; 64];
+    let args = [
+        ArgView::Blob(&[1, 2, 3, 4, 5]),
+        ArgView::Bool(true),
+        ArgView::Symbol("abc"),
+    ];
+    let len = encode_message_into("/xyz", &args, &mut buf).unwrap();
+    assert_eq!(
+        &buf[..len],
+        b"/xyz\0\0\0\0,bTS\0\0\0\0\0\0\0\x05\x01\x02\x03\x04\x05\0\0\0abc\0"
+    );
+}
+
+#[test]
+fn encoded_message_len_matches_encode_message_into() {
+    let view = MessageView::new(MESSAGE).unwrap();
+    let mut args = [ArgView::Nil, ArgView::Nil];
+    for (slot, arg) in args.iter_mut().zip(view.args()) {
+        *slot = arg.unwrap();
+    }
+    assert_eq!(encoded_message_len("/a", &args), Ok(MESSAGE.len()));
+    assert_eq!(encoded_message_len("/abcd", &[]), Ok(12));
+}
+
+#[test]
+fn bundle_writer_back_patches_element_sizes() {
+    let mut buf = [0u8; 128];
+    let mut writer = BundleWriter::new(&mut buf, 2).unwrap();
+    writer.message("/a", &[ArgView::Int(7)]).unwrap();
+    writer
+        .bundle(3, |inner| {
+            inner.message("/b", &[])?;
+            inner.bundle(4, |_| Ok(()))
+        })
+        .unwrap();
+    let len = writer.finish();
+
+    let mut expected = [0u8; 128];
+    let parts: [&[u8]; 5] = [
+        b"#bundle\0\0\0\0\0\0\0\0\x02",
+        b"\0\0\0\x0c/a\0\0,i\0\0\0\0\0\x07",
+        b"\0\0\0\x30#bundle\0\0\0\0\0\0\0\0\x03",
+        b"\0\0\0\x08/b\0\0,\0\0\0",
+        b"\0\0\0\x10#bundle\0\0\0\0\0\0\0\0\x04",
+    ];
+    let mut expected_len = 0;
+    for part in parts {
+        expected[expected_len..expected_len + part.len()].copy_from_slice(part);
+        expected_len += part.len();
+    }
+    assert_eq!(&buf[..len], &expected[..expected_len]);
+
+    let Ok(PacketView::Bundle(view)) = PacketView::new(&buf[..len]) else {
+        panic!("expected a bundle");
+    };
+    assert_eq!(view.elements().count(), 2);
+}
+
+#[test]
+fn bundle_writer_reports_buffer_too_small() {
+    let mut buf = [0u8; 24];
+    assert_eq!(
+        BundleWriter::new(&mut buf[..15], 1).unwrap_err().kind(),
+        ErrorKind::BufferTooSmall
+    );
+
+    let mut writer = BundleWriter::new(&mut buf, 1).unwrap();
+    let err = writer.message("/a", &[ArgView::Int(1)]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BufferTooSmall);
+}
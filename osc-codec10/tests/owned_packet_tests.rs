@@ -0,0 +1,76 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{encode_bundle, encode_message, ErrorKind, OwnedPacket};
+use osc_types10::{Bundle, Message, OscPacket, OscType};
+use std::sync::mpsc;
+use std::thread;
+
+fn message_bytes() -> Vec<u8> {
+    encode_message(&Message::new(
+        "/sensor/raw",
+        vec![OscType::Blob(&[1, 2, 3, 4, 5]), OscType::String("label")],
+    ))
+}
+
+#[test]
+fn borrows_args_from_the_owned_buffer() {
+    let owned = OwnedPacket::from_vec(message_bytes()).unwrap();
+
+    let OscPacket::Message(msg) = owned.packet() else {
+        panic!("Expected message");
+    };
+    let OscType::Blob(blob) = msg.args[0] else {
+        panic!("Expected blob");
+    };
+    // The blob points into the buffer rather than into a copy.
+    let buf = owned.as_bytes().as_ptr_range();
+    assert!(buf.contains(&blob.as_ptr()));
+    assert_eq!(blob, [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn moves_across_threads_and_channels() {
+    let (tx, rx) = mpsc::channel::<OwnedPacket>();
+    let bundle = Bundle::with_messages(9, vec![Message::new("/a", vec![OscType::Int(1)])]);
+    let bytes = encode_bundle(&bundle);
+
+    let receiver = thread::spawn(move || {
+        let owned = rx.recv().unwrap();
+        owned.packet().clone().into_owned()
+    });
+    tx.send(OwnedPacket::from_vec(bytes).unwrap()).unwrap();
+
+    let received = receiver.join().unwrap();
+    assert_eq!(OscPacket::from(&received), OscPacket::Bundle(bundle));
+}
+
+#[test]
+fn clone_and_into_vec() {
+    let bytes = message_bytes();
+    let owned = OwnedPacket::try_from(bytes.clone()).unwrap();
+
+    assert_eq!(owned.clone(), owned);
+    assert_eq!(owned.into_vec(), bytes);
+}
+
+#[test]
+fn rejects_invalid_and_trailing_bytes() {
+    let mut bytes = message_bytes();
+    bytes.extend_from_slice(&[0, 0, 0, 0]);
+    assert_eq!(
+        OwnedPacket::from_vec(bytes).unwrap_err().kind(),
+        ErrorKind::TrailingBytes
+    );
+
+    assert!(OwnedPacket::from_vec(vec![b'/', b'a']).is_err());
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn from_bytes_shares_the_buffer() {
+    let bytes = bytes::Bytes::from(message_bytes());
+    let owned = OwnedPacket::from_bytes(bytes.clone()).unwrap();
+
+    assert_eq!(owned.as_bytes().as_ptr(), bytes.as_ptr());
+    assert!(matches!(owned.packet(), OscPacket::Message(m) if m.address == "/sensor/raw"));
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{decode_bundle, decode_message, encode_bundle, encode_message, encode_packet};
+use osc_types10::{Bundle, BundleBuf, Message, MessageBuf, OscPacket, OscType, OscTypeBuf};
+use std::sync::mpsc;
+use std::thread;
+
+#[test]
+fn owned_message_encodes_like_borrowed() {
+    let owned = MessageBuf::new(
+        "/synth/name",
+        vec![
+            OscTypeBuf::String("lead".into()),
+            OscTypeBuf::Blob(vec![1, 2, 3]),
+            OscTypeBuf::Array(vec![OscTypeBuf::Int(1), OscTypeBuf::Symbol("x".into())]),
+        ],
+    );
+
+    let bytes = encode_packet(&owned);
+    assert_eq!(bytes, encode_message(&Message::from(&owned)));
+
+    let (decoded, _) = decode_message(&bytes).unwrap();
+    assert_eq!(decoded.into_owned(), owned);
+}
+
+#[test]
+fn owned_bundle_encodes_like_borrowed() {
+    let mut bundle = Bundle::with_messages(7, vec![Message::new("/a", vec![OscType::Int(1)])]);
+    bundle.add_bundle(Bundle::empty(8));
+    let owned = BundleBuf::from(&bundle);
+
+    let bytes = encode_packet(&owned);
+    assert_eq!(bytes, encode_bundle(&bundle));
+}
+
+#[test]
+fn decoded_packets_can_outlive_the_receive_buffer() {
+    let (tx, rx) = mpsc::channel();
+    let mut buf = encode_bundle(&Bundle::with_messages(
+        1,
+        vec![Message::new("/queued", vec![OscType::String("payload")])],
+    ));
+
+    let (bundle, _) = decode_bundle(&buf).unwrap();
+    tx.send(bundle.into_owned()).unwrap();
+    // The receive buffer can now be reused.
+    buf.fill(0);
+
+    let received = thread::spawn(move || rx.recv().unwrap()).join().unwrap();
+    match Bundle::from(&received).packets.as_slice() {
+        [OscPacket::Message(msg)] => {
+            assert_eq!(msg.address, "/queued");
+            assert_eq!(msg.args, vec![OscType::String("payload")]);
+        }
+        other => panic!("unexpected packets {other:?}"),
+    }
+}
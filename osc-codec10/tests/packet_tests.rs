@@ -0,0 +1,77 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{decode_packet, encode_bundle, encode_message, encode_packet, ErrorKind};
+use osc_types10::{Bundle, Message, OscPacket, OscType};
+
+#[test]
+fn roundtrip_message_packet() {
+    let packet = OscPacket::Message(Message::new("/synth/freq", vec![OscType::Float(440.0)]));
+    let bytes = encode_packet(&packet);
+
+    assert_eq!(decode_packet(&bytes).unwrap(), packet);
+}
+
+#[test]
+fn roundtrip_bundle_packet() {
+    let mut bundle = Bundle::with_messages(3, vec![Message::new("/a", vec![OscType::Int(1)])]);
+    bundle.add_bundle(Bundle::with_messages(4, vec![Message::new("/b", vec![])]));
+    let packet = OscPacket::Bundle(bundle);
+    let bytes = encode_packet(&packet);
+
+    assert_eq!(decode_packet(&bytes).unwrap(), packet);
+}
+
+#[test]
+fn encode_packet_matches_specific_encoders() {
+    let msg = Message::new("/x", vec![OscType::Nil]);
+    let bundle = Bundle::with_messages(1, vec![msg.clone()]);
+
+    assert_eq!(
+        encode_packet(&OscPacket::Message(msg.clone())),
+        encode_message(&msg)
+    );
+    assert_eq!(
+        encode_packet(&OscPacket::Bundle(bundle.clone())),
+        encode_bundle(&bundle)
+    );
+}
+
+#[test]
+fn rejects_trailing_bytes_after_message() {
+    let mut bytes = encode_message(&Message::new("/a", vec![OscType::Int(1)]));
+    bytes.extend_from_slice(&[0, 0, 0, 0]);
+
+    let err = decode_packet(&bytes).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TrailingBytes);
+    assert_eq!(err.offset(), bytes.len() - 4);
+}
+
+#[test]
+fn rejects_trailing_bytes_after_bundle() {
+    let mut bytes = encode_bundle(&Bundle::with_messages(1, vec![Message::new("/a", vec![])]));
+    bytes.extend_from_slice(&[0, 0]);
+
+    assert!(decode_packet(&bytes).is_err());
+}
+
+#[test]
+fn only_the_bundle_tag_selects_bundle_decoding() {
+    // Addresses that merely start with '#' or "#bundle" are messages.
+    for address in ["#bundled", "#other", "/#bundle"] {
+        let msg = Message::new(address, vec![OscType::Int(5)]);
+        let bytes = encode_message(&msg);
+        assert_eq!(decode_packet(&bytes).unwrap(), OscPacket::Message(msg));
+    }
+
+    // A leading "#bundle\0" is always a bundle, even if it is not a valid one.
+    let msg = Message::new("#bundle", vec![OscType::String("not a bundle")]);
+    assert!(!matches!(
+        decode_packet(&encode_message(&msg)),
+        Ok(OscPacket::Message(_))
+    ));
+}
+
+#[test]
+fn rejects_empty_input() {
+    assert!(decode_packet(&[]).is_err());
+}
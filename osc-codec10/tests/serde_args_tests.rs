@@ -0,0 +1,288 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use osc_codec10::{
+    decode_message, encode_message, from_args, from_message, from_slice, to_args, to_message,
+    to_vec, ArgsErrorKind, ErrorKind,
+};
+use osc_types10::{Message, OscType, OscTypeBuf, TimeTag};
+use serde::{Deserialize, Serialize};
+use serde_bytes::{ByteBuf, Bytes};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Voice<'a> {
+    id: u8,
+    gain: f32,
+    detune: f64,
+    name: &'a str,
+    #[serde(borrow)]
+    sample: &'a Bytes,
+    on: bool,
+    offset: i64,
+    steps: Vec<i32>,
+    env: (f32, f32),
+    wave: Wave,
+    filter: Option<Filter>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Filter {
+    cutoff: f32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Wave {
+    Sine,
+    Pulse(f32),
+    Custom { points: Vec<f32>, smooth: bool },
+}
+
+fn voice() -> Voice<'static> {
+    Voice {
+        id: 3,
+        gain: 0.5,
+        detune: -1.25,
+        name: "lead",
+        sample: Bytes::new(&[1, 2, 3]),
+        on: true,
+        offset: -7,
+        steps: vec![1, 2],
+        env: (0.1, 0.2),
+        wave: Wave::Pulse(0.25),
+        filter: Some(Filter { cutoff: 800.0 }),
+    }
+}
+
+#[test]
+fn flattens_structs_into_arguments() {
+    let args = to_args(&voice()).unwrap();
+    assert_eq!(
+        args,
+        [
+            OscTypeBuf::Int(3),
+            OscTypeBuf::Float(0.5),
+            OscTypeBuf::Double(-1.25),
+            OscTypeBuf::String("lead".into()),
+            OscTypeBuf::Blob(vec![1, 2, 3]),
+            OscTypeBuf::Bool(true),
+            OscTypeBuf::Long(-7),
+            OscTypeBuf::Array(vec![OscTypeBuf::Int(1), OscTypeBuf::Int(2)]),
+            OscTypeBuf::Float(0.1),
+            OscTypeBuf::Float(0.2),
+            OscTypeBuf::String("Pulse".into()),
+            OscTypeBuf::Float(0.25),
+            OscTypeBuf::Float(800.0),
+        ]
+    );
+}
+
+#[test]
+fn round_trips_through_the_codec() {
+    let bytes = to_vec("/voice", &voice()).unwrap();
+    let msg = to_message("/voice", &voice()).unwrap();
+    assert_eq!(bytes, encode_message(&Message::from(&msg)));
+
+    let (decoded, _) = decode_message(&bytes).unwrap();
+    assert_eq!(decoded.address, "/voice");
+    assert_eq!(from_message::<Voice<'_>>(&decoded).unwrap(), voice());
+    assert_eq!(from_slice::<Voice<'_>>(&bytes).unwrap(), voice());
+}
+
+#[test]
+fn borrows_strings_and_blobs() {
+    let bytes = to_vec("/voice", &voice()).unwrap();
+    let decoded: Voice<'_> = from_slice(&bytes).unwrap();
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&decoded.name.as_ptr()));
+    assert!(range.contains(&decoded.sample.as_ptr()));
+}
+
+#[test]
+fn enums_and_options() {
+    let custom = Wave::Custom {
+        points: vec![0.0, 1.0],
+        smooth: false,
+    };
+    let args = to_args(&custom).unwrap();
+    assert_eq!(
+        args,
+        [
+            OscTypeBuf::String("Custom".into()),
+            OscTypeBuf::Array(vec![OscTypeBuf::Float(0.0), OscTypeBuf::Float(1.0)]),
+            OscTypeBuf::Bool(false),
+        ]
+    );
+    let msg = to_message("/w", &custom).unwrap();
+    assert_eq!(from_message::<Wave>(&Message::from(&msg)).unwrap(), custom);
+    assert_eq!(
+        from_args::<Wave>(&[OscType::Symbol("Sine")]).unwrap(),
+        Wave::Sine
+    );
+
+    assert_eq!(to_args(&None::<i32>).unwrap(), [OscTypeBuf::Nil]);
+    assert_eq!(
+        from_args::<(i32, Option<i32>)>(&[OscType::Int(1), OscType::Nil]).unwrap(),
+        (1, None)
+    );
+    // Missing trailing arguments read as `None`.
+    assert_eq!(
+        from_args::<(i32, Option<i32>)>(&[OscType::Int(1)]).unwrap(),
+        (1, None)
+    );
+}
+
+#[test]
+fn sequences_and_maps_become_arrays() {
+    let nested = vec![vec![1u8], vec![]];
+    let args = to_args(&nested).unwrap();
+    assert_eq!(
+        args,
+        [OscTypeBuf::Array(vec![
+            OscTypeBuf::Array(vec![OscTypeBuf::Int(1)]),
+            OscTypeBuf::Array(vec![]),
+        ])]
+    );
+    let msg = to_message("/n", &nested).unwrap();
+    assert_eq!(
+        from_message::<Vec<Vec<u8>>>(&Message::from(&msg)).unwrap(),
+        nested
+    );
+
+    let map = BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    let msg = to_message("/m", &map).unwrap();
+    assert_eq!(
+        msg.args,
+        [OscTypeBuf::Array(vec![
+            OscTypeBuf::String("a".into()),
+            OscTypeBuf::Int(1),
+            OscTypeBuf::String("b".into()),
+            OscTypeBuf::Int(2),
+        ])]
+    );
+    assert_eq!(
+        from_message::<BTreeMap<String, i32>>(&Message::from(&msg)).unwrap(),
+        map
+    );
+
+    let blob = ByteBuf::from(vec![9, 8]);
+    assert_eq!(to_args(&blob).unwrap(), [OscTypeBuf::Blob(vec![9, 8])]);
+}
+
+#[test]
+fn integer_widths() {
+    assert_eq!(
+        to_args(&(1i8, 2u16, 3u32, 4u64)).unwrap(),
+        [
+            OscTypeBuf::Int(1),
+            OscTypeBuf::Int(2),
+            OscTypeBuf::Long(3),
+            OscTypeBuf::Long(4),
+        ]
+    );
+    let err = to_args(&u64::MAX).unwrap_err();
+    assert_eq!(err.kind(), &ArgsErrorKind::OutOfRange);
+    let err = to_args(&1u128).unwrap_err();
+    assert_eq!(err.kind(), &ArgsErrorKind::Unsupported("u128"));
+
+    assert_eq!(
+        from_args::<(i64, u32)>(&[OscType::Int(-1), OscType::Long(5)]).unwrap(),
+        (-1, 5)
+    );
+    let err = from_args::<(u8, u8)>(&[OscType::Int(1), OscType::Int(256)]).unwrap_err();
+    assert_eq!(err.kind(), &ArgsErrorKind::OutOfRange);
+    assert_eq!(err.index(), Some(1));
+}
+
+#[test]
+fn time_tags_are_t_arguments() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Cue {
+        at: TimeTag,
+    }
+
+    let cue = Cue {
+        at: TimeTag::new(3_913_056_000, 0),
+    };
+    let args = to_args(&cue).unwrap();
+    assert_eq!(args, [OscTypeBuf::Time(cue.at)]);
+    let bytes = to_vec("/cue", &cue).unwrap();
+    assert_eq!(from_slice::<Cue>(&bytes).unwrap(), cue);
+
+    let late = Cue {
+        at: TimeTag::from_bits(u64::MAX),
+    };
+    assert_eq!(to_args(&late).unwrap(), [OscTypeBuf::Time(late.at)]);
+
+    let err = from_args::<Cue>(&[OscType::Long(1)]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ArgsErrorKind::WrongType {
+            expected: "t",
+            actual: 'h'
+        }
+    );
+}
+
+#[test]
+fn reports_mismatched_arguments() {
+    let args = [OscType::Int(1), OscType::Array(vec![OscType::Float(1.0)])];
+
+    let err = from_args::<(i32, Vec<i32>)>(&args).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ArgsErrorKind::WrongType {
+            expected: "ih",
+            actual: 'f'
+        }
+    );
+    assert_eq!(err.index(), Some(1));
+
+    let err = from_args::<(i32, Vec<f32>, i32)>(&args).unwrap_err();
+    assert_eq!(err.kind(), &ArgsErrorKind::Missing);
+    assert_eq!(err.index(), Some(2));
+
+    let err = from_args::<i32>(&args).unwrap_err();
+    assert_eq!(err.kind(), &ArgsErrorKind::Extra { actual: '[' });
+    assert_eq!(err.to_string(), "unexpected argument '[' at argument 1");
+
+    let err = from_args::<Wave>(&[OscType::String("Saw")]).unwrap_err();
+    assert!(matches!(err.kind(), ArgsErrorKind::Custom(_)));
+    assert_eq!(err.index(), Some(0));
+
+    let mut bytes = to_vec("/a", &1).unwrap();
+    bytes.extend_from_slice(&[0; 4]);
+    let err = from_slice::<i32>(&bytes).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ArgsErrorKind::Decode(e) if e.kind() == ErrorKind::TrailingBytes
+    ));
+}
+
+#[test]
+fn reads_hand_built_messages() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Fader<'a> {
+        channel: i32,
+        label: &'a str,
+        level: f64,
+    }
+
+    let msg = Message::new(
+        "/fader",
+        vec![
+            OscType::Int(4),
+            OscType::Symbol("bass"),
+            OscType::Float(0.5),
+        ],
+    );
+    let bytes = encode_message(&msg);
+    assert_eq!(
+        from_slice::<Fader<'_>>(&bytes).unwrap(),
+        Fader {
+            channel: 4,
+            label: "bass",
+            level: 0.5
+        }
+    );
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{decode_packet, encode_packet};
+use osc_types10::OscPacketBuf;
+
+const PACKETS: &[&str] = &[
+    "/synth/freq ,fi 440.0 1",
+    "/empty",
+    "/all ,ifsbhtdScrmTFNI[i[]N] -1 0.25 \"a \\\"b\\\"\\n\" 0x00ff10 \
+     -9000000000 1 1e-300 \"sym\" 'é' #ff000080 01:b0:07:7f [2 []]",
+    "/blob ,b 0x",
+    "#bundle 1 [ ]",
+    "#bundle 16045690984833335296 [ /a ,s \"x\" #bundle 1 [ /b ,T ] /c ,d NaN ]",
+];
+
+#[test]
+fn text_round_trips_through_the_codec() {
+    for text in PACKETS {
+        let packet: OscPacketBuf = text.parse().unwrap();
+        let bytes = encode_packet(&packet);
+        let decoded = decode_packet(&bytes).unwrap();
+        assert_eq!(decoded.to_string(), *text);
+        assert_eq!(encode_packet(&decoded), bytes);
+    }
+}
+
+#[test]
+fn decoded_packets_round_trip_through_text() {
+    let bytes = b"/a\0\0,fb\0\x7f\xc0\x00\x01\0\0\0\x02\xab\xcd\0\0";
+    let packet = decode_packet(bytes).unwrap();
+    let text = packet.to_string();
+    assert_eq!(text, "/a ,fb NaN:0x7fc00001 0xabcd");
+    let parsed: OscPacketBuf = text.parse().unwrap();
+    assert_eq!(encode_packet(&parsed), bytes);
+}
@@ -0,0 +1,268 @@
+#![cfg(feature = "alloc")]
+
+use osc_codec10::{
+    decode_message, decode_message_with, encode_bundle, encode_message, encode_message_into,
+    ArgView, BundleView, DecodeOptions, ErrorKind, Limits, MessageView, PacketView,
+};
+use osc_types10::{Bundle, Message, OscType};
+
+fn message() -> Message<'static> {
+    Message::new(
+        "/mix/1",
+        vec![
+            OscType::Int(3),
+            OscType::Array(vec![
+                OscType::String("a"),
+                OscType::Array(vec![OscType::Blob(&[1, 2, 3])]),
+                OscType::Nil,
+            ]),
+            OscType::Double(0.5),
+        ],
+    )
+}
+
+fn options(limits: Limits) -> DecodeOptions {
+    DecodeOptions::new().with_limits(limits)
+}
+
+#[test]
+fn iterates_arguments_lazily() {
+    let bytes = encode_message(&message());
+    let view = MessageView::new(&bytes).unwrap();
+
+    assert_eq!(view.address(), "/mix/1");
+    assert_eq!(view.type_tags(), "i[s[b]N]d");
+
+    let mut args = view.args();
+    assert!(matches!(args.next(), Some(Ok(ArgView::Int(3)))));
+    let Some(Ok(ArgView::Array(mut items))) = args.next() else {
+        panic!("expected array");
+    };
+    // The argument after the array is reachable without walking into it.
+    assert!(matches!(args.next(), Some(Ok(ArgView::Double(d))) if d == 0.5));
+    assert!(args.next().is_none());
+
+    assert!(matches!(items.next(), Some(Ok(ArgView::String("a")))));
+    let Some(Ok(ArgView::Array(mut inner))) = items.next() else {
+        panic!("expected nested array");
+    };
+    assert!(matches!(inner.next(), Some(Ok(ArgView::Blob(&[1, 2, 3])))));
+    assert!(inner.next().is_none());
+    assert!(matches!(items.next(), Some(Ok(ArgView::Nil))));
+    assert!(items.next().is_none());
+}
+
+#[test]
+fn converts_to_owned_types() {
+    let msg = message();
+    let bytes = encode_message(&msg);
+    let view = MessageView::new(&bytes).unwrap();
+
+    assert_eq!(view.to_message().unwrap(), msg);
+    let args: Vec<OscType<'_>> = view
+        .args()
+        .map(|arg| arg.unwrap().to_osc_type().unwrap())
+        .collect();
+    assert_eq!(args, msg.args);
+    assert_eq!(
+        view.args()
+            .map(|arg| arg.unwrap().tag())
+            .collect::<String>(),
+        "i[d"
+    );
+}
+
+#[test]
+fn validates_header_and_reports_bad_data_lazily() {
+    let err = MessageView::new(b"/a\0\0,i]\0").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnbalancedArray);
+    assert_eq!(err.offset(), 6);
+    let err = MessageView::new(b"/a\0\0,[i[\0\0\0\0").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnbalancedArray);
+    assert_eq!(err.arg_index(), Some(2));
+    assert_eq!(
+        MessageView::new(b"/a\0\0,x\0\0").unwrap_err().kind(),
+        ErrorKind::UnknownTypeTag
+    );
+
+    // Header is fine, the second argument is cut short.
+    let bytes = encode_message(&Message::new("/a", vec![OscType::Int(1), OscType::Long(2)]));
+    let view = MessageView::new(&bytes[..bytes.len() - 4]).unwrap();
+    let mut args = view.args();
+    assert!(matches!(args.next(), Some(Ok(ArgView::Int(1)))));
+    let err = args.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(err.arg_index(), Some(1));
+    assert!(args.next().is_none());
+    assert_eq!(
+        view.to_message().unwrap_err(),
+        decode_message(&bytes[..bytes.len() - 4]).unwrap_err()
+    );
+
+    // Bad data inside an array surfaces when the array is reached.
+    let mut bytes = encode_message(&Message::new(
+        "/a",
+        vec![OscType::Array(vec![OscType::Char('x')])],
+    ));
+    let len = bytes.len();
+    bytes[len - 4..].copy_from_slice(&0xd800u32.to_be_bytes());
+    let err = MessageView::new(&bytes)
+        .unwrap()
+        .args()
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidChar);
+    assert_eq!(err.arg_index(), Some(1));
+}
+
+#[test]
+fn iterates_bundle_elements() {
+    let mut outer = Bundle::with_messages(5, vec![Message::new("/first", vec![])]);
+    outer.add_bundle(Bundle::with_messages(6, vec![message()]));
+    let bytes = encode_bundle(&outer);
+
+    let PacketView::Bundle(view) = PacketView::new(&bytes).unwrap() else {
+        panic!("expected bundle");
+    };
+    assert_eq!(view.timetag(), 5);
+
+    let mut elements = view.elements();
+    let Some(Ok(PacketView::Message(first))) = elements.next() else {
+        panic!("expected message");
+    };
+    assert_eq!(first.address(), "/first");
+    let Some(Ok(PacketView::Bundle(inner))) = elements.next() else {
+        panic!("expected bundle");
+    };
+    assert!(elements.next().is_none());
+
+    assert_eq!(inner.timetag(), 6);
+    let addresses: Vec<&str> = inner
+        .elements()
+        .map(|e| match e.unwrap() {
+            PacketView::Message(msg) => msg.address(),
+            PacketView::Bundle(_) => panic!("expected message"),
+        })
+        .collect();
+    assert_eq!(addresses, ["/mix/1"]);
+}
+
+#[test]
+fn reports_malformed_bundle_elements() {
+    assert_eq!(
+        BundleView::new(b"#bundle\0\0\0").unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+
+    let mut bytes = encode_bundle(&Bundle::with_messages(
+        1,
+        vec![Message::new("/a", vec![]), Message::new("/b", vec![])],
+    ));
+    // Corrupt the type tags of the second element.
+    let len = bytes.len();
+    bytes[len - 4] = b'x';
+    let view = BundleView::new(&bytes).unwrap();
+    let mut elements = view.elements();
+    assert!(elements.next().unwrap().is_ok());
+    let err = elements.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingTypeTags);
+    assert_eq!(err.path(), &[1]);
+    assert_eq!(err.offset(), len - 4);
+    assert!(elements.next().is_none());
+
+    bytes[16..20].copy_from_slice(&1000i32.to_be_bytes());
+    let err = BundleView::new(&bytes)
+        .unwrap()
+        .elements()
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidElementSize);
+}
+
+/// A message `/a` with `depth` empty arrays nested in each other.
+fn nested_arrays(depth: usize) -> Vec<u8> {
+    let mut bytes = b"/a\0\0,".to_vec();
+    bytes.resize(bytes.len() + depth, b'[');
+    bytes.resize(bytes.len() + depth, b']');
+    let padded = (bytes.len() + 4) & !3;
+    bytes.resize(padded, 0);
+    bytes
+}
+
+#[test]
+fn limits_array_nesting() {
+    let err = MessageView::new(&nested_arrays(200_000)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NestingTooDeep);
+    assert_eq!(err.offset(), 5 + 32);
+    assert_eq!(err.arg_index(), Some(32));
+
+    let bytes = nested_arrays(40);
+    assert!(MessageView::new(&bytes).is_err());
+    let limits = Limits {
+        max_depth: 40,
+        ..Limits::default()
+    };
+    let view = MessageView::with_limits(&bytes, &limits).unwrap();
+    assert_eq!(
+        view.to_message().unwrap(),
+        decode_message_with(&bytes, &options(limits)).unwrap().0
+    );
+    let arg = view.args().next().unwrap().unwrap();
+    let mut depth = 0;
+    let mut value = arg.to_osc_type().unwrap();
+    while let OscType::Array(mut items) = value {
+        depth += 1;
+        value = items.pop().unwrap_or(OscType::Nil);
+    }
+    assert_eq!(depth, 40);
+    let mut buf = [0u8; 96];
+    let len = encode_message_into("/a", &[arg], &mut buf).unwrap();
+    assert_eq!(&buf[..len], &bytes[..]);
+
+    // Elements of a bundle view are checked with the bundle's limits.
+    let mut bundle = b"#bundle\0\0\0\0\0\0\0\0\x01".to_vec();
+    bundle.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+    bundle.extend_from_slice(&bytes);
+    let element = |limits: &Limits| {
+        BundleView::with_limits(&bundle, limits)
+            .unwrap()
+            .elements()
+            .next()
+            .unwrap()
+    };
+    assert_eq!(
+        element(&Limits::default()).unwrap_err().kind(),
+        ErrorKind::NestingTooDeep
+    );
+    assert!(element(&limits).is_ok());
+    assert!(PacketView::with_limits(&bundle, &limits).is_ok());
+}
+
+#[test]
+fn to_message_applies_the_view_limits() {
+    let bytes = encode_message(&Message::new(
+        "/a",
+        vec![OscType::String("abcdef"), OscType::Blob(&[1, 2, 3])],
+    ));
+    let view = |limits: Limits| MessageView::with_limits(&bytes, &limits).unwrap();
+
+    assert!(view(Limits::default()).to_message().is_ok());
+    let strings = Limits {
+        max_string_size: 4,
+        ..Limits::default()
+    };
+    assert_eq!(
+        view(strings).to_message().unwrap_err(),
+        decode_message_with(&bytes, &options(strings)).unwrap_err()
+    );
+    let blobs = Limits {
+        max_blob_size: 2,
+        ..Limits::default()
+    };
+    assert_eq!(
+        view(blobs).to_message().unwrap_err().kind(),
+        ErrorKind::BlobTooLarge
+    );
+}
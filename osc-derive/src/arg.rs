@@ -0,0 +1,133 @@
+//! `OscArg` for fieldless enums.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr};
+
+/// How an enum is carried.
+enum Repr {
+    Int,
+    String,
+}
+
+fn repr_attr(attrs: &[Attribute]) -> syn::Result<Repr> {
+    let mut repr = Repr::Int;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("osc")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("repr") {
+                return Err(meta.error("unknown osc attribute, expected `repr`"));
+            }
+            let value = meta.value()?.parse::<LitStr>()?;
+            repr = match value.value().as_str() {
+                "int" => Repr::Int,
+                "string" => Repr::String,
+                _ => {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "expected `repr = \"int\"` or `repr = \"string\"`",
+                    ))
+                }
+            };
+            Ok(())
+        })?;
+    }
+    Ok(repr)
+}
+
+/// The string a variant is sent as: its name or `#[osc(rename = "...")]`.
+fn variant_name(variant: &syn::Variant) -> syn::Result<LitStr> {
+    let mut name = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("osc"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                name = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unknown osc attribute, expected `rename`"))
+            }
+        })?;
+    }
+    Ok(name)
+}
+
+pub(crate) fn osc_arg(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "OscArg can only be derived for fieldless enums",
+        ));
+    };
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new_spanned(
+            variant,
+            "OscArg can only be derived for enums without fields",
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants: Vec<_> = data.variants.iter().map(|variant| &variant.ident).collect();
+    let invalid = quote!(::osc_types10::ArgError::invalid_value(arg));
+
+    let (to, from) = match repr_attr(&input.attrs)? {
+        Repr::Int => (
+            quote! {
+                ::osc_types10::OscTypeBuf::Int(match self {
+                    #(Self::#variants => Self::#variants as i32,)*
+                })
+            },
+            quote! {
+                let value = <i32 as ::osc_types10::OscArg>::from_osc_arg(arg)?;
+                #(
+                    if value == Self::#variants as i32 {
+                        return Ok(Self::#variants);
+                    }
+                )*
+                Err(#invalid)
+            },
+        ),
+        Repr::String => {
+            let names = data
+                .variants
+                .iter()
+                .map(variant_name)
+                .collect::<syn::Result<Vec<_>>>()?;
+            (
+                quote! {
+                    ::osc_types10::OscTypeBuf::String(match self {
+                        #(Self::#variants => #names,)*
+                    }.into())
+                },
+                quote! {
+                    let value: &str = ::core::convert::TryFrom::try_from(arg)?;
+                    match value {
+                        #(#names => Ok(Self::#variants),)*
+                        _ => Err(#invalid),
+                    }
+                },
+            )
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::osc_types10::OscArg for #name #ty_generics #where_clause {
+            fn to_osc_arg(&self) -> ::osc_types10::OscTypeBuf {
+                #to
+            }
+
+            fn from_osc_arg(
+                arg: &::osc_types10::OscType<'_>,
+            ) -> ::core::result::Result<Self, ::osc_types10::ArgError> {
+                #from
+            }
+        }
+    })
+}
@@ -0,0 +1,162 @@
+#![doc = r#"
+Derive macros for the message mapping traits of
+[`osc-types10`](https://docs.rs/osc-types10).
+
+Use them through `osc-types10` with its `derive` feature, which re-exports
+them next to the traits they implement:
+
+```toml
+osc-types10 = { version = "0.1.0-alpha.2", features = ["derive"] }
+```
+
+```rust
+use osc_types10::{osc, FromOscMessage, Message, OscArg, ToOscMessage};
+
+#[derive(Debug, PartialEq, OscArg)]
+#[osc(repr = "string")]
+enum Wave {
+    Sine,
+    Square,
+}
+
+#[derive(Debug, PartialEq, ToOscMessage, FromOscMessage)]
+#[osc(address = "/synth/{id}/params")]
+struct Params {
+    id: u32,
+    freq: f32,
+    wave: Wave,
+}
+
+let params = Params { id: 3, freq: 440.0, wave: Wave::Square };
+let msg = params.to_osc_message().unwrap();
+assert_eq!(Message::from(&msg), osc!("/synth/3/params", 440.0f32, "Square"));
+assert_eq!(Params::from_osc_message(&Message::from(&msg)), Ok(params));
+```
+
+## Structs
+`ToOscMessage` and `FromOscMessage` need `#[osc(address = "...")]`. Each
+`{field}` in the address (`{0}` for tuple structs) is filled in with the
+field's `Display` impl and parsed back with its `FromStr` impl. The formatted
+value must be a single valid address component: `to_osc_message` returns an
+`AddressError` if it is empty, contains `/`, or contains a character an OSC
+address may not hold (space, `#`, `,`, control characters, non-ASCII or a
+pattern character). The text around the fields is checked by the same rules
+when the derive expands, and each field must be followed by `/` or end the
+address, so that its value can be told apart from the text after it.
+
+The remaining fields map to the arguments in declaration order; their types
+must implement `OscArg`, so a field with no OSC representation fails to
+compile:
+
+```compile_fail
+use osc_types10::ToOscMessage;
+
+#[derive(ToOscMessage)]
+#[osc(address = "/a")]
+struct Bad {
+    value: u8,
+}
+```
+
+`FromOscMessage` requires the address to match and the number of arguments to
+equal the number of argument fields.
+
+## Enums
+Fieldless enums derive `OscArg` and are sent as an int (`#[osc(repr = "int")]`,
+the default) holding the discriminant, or as a string
+(`#[osc(repr = "string")]`) holding the variant name, which
+`#[osc(rename = "...")]` on a variant overrides.
+"#]
+#![forbid(unsafe_code)]
+#![deny(missing_docs, rust_2018_idioms)]
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod arg;
+mod message;
+
+/// Derive `osc_types10::ToOscMessage`; see the [crate docs](crate).
+///
+/// The address must be a valid method address once its fields are filled in:
+///
+/// ```compile_fail
+/// use osc_types10::ToOscMessage;
+///
+/// #[derive(ToOscMessage)]
+/// #[osc(address = "/a b/{id}")]
+/// struct Bad {
+///     id: u32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use osc_types10::ToOscMessage;
+///
+/// #[derive(ToOscMessage)]
+/// #[osc(address = "/synth*/{id}")]
+/// struct Bad {
+///     id: u32,
+/// }
+/// ```
+#[proc_macro_derive(ToOscMessage, attributes(osc))]
+pub fn derive_to_osc_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    message::to_message(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `osc_types10::FromOscMessage`; see the [crate docs](crate).
+///
+/// Every `{field}` in the address must name a field:
+///
+/// ```compile_fail
+/// use osc_types10::FromOscMessage;
+///
+/// #[derive(FromOscMessage)]
+/// #[osc(address = "/a/{missing}")]
+/// struct Bad {
+///     value: i32,
+/// }
+/// ```
+///
+/// Each field must be followed by `/` or end the address, as `/a/-1-2` could
+/// not be split back into `x = -1` and `y = 2`:
+///
+/// ```compile_fail
+/// use osc_types10::FromOscMessage;
+///
+/// #[derive(FromOscMessage)]
+/// #[osc(address = "/a/{x}-{y}")]
+/// struct Bad {
+///     x: i32,
+///     y: i32,
+/// }
+/// ```
+#[proc_macro_derive(FromOscMessage, attributes(osc))]
+pub fn derive_from_osc_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    message::from_message(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `osc_types10::OscArg` for a fieldless enum; see the
+/// [crate docs](crate).
+///
+/// ```compile_fail
+/// use osc_types10::OscArg;
+///
+/// #[derive(OscArg)]
+/// enum Bad {
+///     Value(i32),
+/// }
+/// ```
+#[proc_macro_derive(OscArg, attributes(osc))]
+pub fn derive_osc_arg(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    arg::osc_arg(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
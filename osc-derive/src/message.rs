@@ -0,0 +1,297 @@
+//! `ToOscMessage` and `FromOscMessage` for structs.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Member, Type};
+
+/// A piece of an address template.
+enum Part {
+    Literal(String),
+    /// Index into the struct's fields.
+    Field(usize),
+}
+
+/// A struct field and where it goes.
+struct Field<'a> {
+    member: Member,
+    ty: &'a Type,
+    /// Whether the field appears in the address instead of the arguments.
+    in_address: bool,
+}
+
+struct Mapping<'a> {
+    fields: Vec<Field<'a>>,
+    address: Vec<Part>,
+    shape: Shape,
+}
+
+/// How the struct is constructed.
+#[derive(Clone, Copy)]
+enum Shape {
+    Named,
+    Tuple,
+    Unit,
+}
+
+impl<'a> Mapping<'a> {
+    fn new(input: &'a DeriveInput) -> syn::Result<Self> {
+        let Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "OSC messages can only be derived for structs",
+            ));
+        };
+        let mut fields: Vec<Field<'a>> = data
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| Field {
+                member: match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(index.into()),
+                },
+                ty: &field.ty,
+                in_address: false,
+            })
+            .collect();
+
+        let template = address_attr(input)?;
+        let address = parse_template(&template, &fields)?;
+        for part in &address {
+            if let Part::Field(index) = *part {
+                fields[index].in_address = true;
+            }
+        }
+        Ok(Self {
+            fields,
+            address,
+            shape: match data.fields {
+                Fields::Named(_) => Shape::Named,
+                Fields::Unnamed(_) => Shape::Tuple,
+                Fields::Unit => Shape::Unit,
+            },
+        })
+    }
+
+    fn args(&self) -> impl Iterator<Item = &Field<'a>> {
+        self.fields.iter().filter(|field| !field.in_address)
+    }
+}
+
+/// The value of `#[osc(address = "...")]`.
+fn address_attr(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut address = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("osc"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("address") {
+                address = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown osc attribute, expected `address`"))
+            }
+        })?;
+    }
+    address.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "missing #[osc(address = \"...\")] attribute",
+        )
+    })
+}
+
+fn parse_template(template: &LitStr, fields: &[Field<'_>]) -> syn::Result<Vec<Part>> {
+    let error = |message: &str| syn::Error::new(template.span(), message);
+    let value = template.value();
+    if !value.starts_with('/') {
+        return Err(error("address must start with '/'"));
+    }
+
+    let mut parts = Vec::new();
+    let mut rest = value.as_str();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('{') {
+            let end = after.find('}').ok_or_else(|| error("unclosed '{'"))?;
+            let name = &after[..end];
+            let index = fields
+                .iter()
+                .position(|field| match &field.member {
+                    Member::Named(ident) => ident == name,
+                    Member::Unnamed(index) => index.index.to_string() == name,
+                })
+                .ok_or_else(|| error(&format!("no field `{name}` for the address")))?;
+            if parts
+                .iter()
+                .any(|part| matches!(part, Part::Field(i) if *i == index))
+            {
+                return Err(error(&format!(
+                    "field `{name}` appears twice in the address"
+                )));
+            }
+            parts.push(Part::Field(index));
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find(['{', '}']).unwrap_or(rest.len());
+            if rest[end..].starts_with('}') {
+                return Err(error("unmatched '}'"));
+            }
+            parts.push(Part::Literal(rest[..end].to_owned()));
+            rest = &rest[end..];
+        }
+    }
+    // A field is read back up to the next '/', so any other text after it
+    // could be mistaken for part of its value.
+    for pair in parts.windows(2) {
+        if let [Part::Field(_), next] = pair {
+            if !matches!(next, Part::Literal(text) if text.starts_with('/')) {
+                return Err(error(
+                    "an address field must be followed by '/' or the end of the address",
+                ));
+            }
+        }
+    }
+    check_literals(&parts).map_err(|message| error(&message))?;
+    Ok(parts)
+}
+
+/// Check the literal text of an address template by the rules of
+/// `osc_types10::validate_address`, with each field standing for a valid
+/// component.
+fn check_literals(parts: &[Part]) -> Result<(), String> {
+    let mut address = String::new();
+    for part in parts {
+        match part {
+            Part::Literal(text) => address.push_str(text),
+            Part::Field(_) => address.push('x'),
+        }
+    }
+    if address.split('/').skip(1).any(str::is_empty) {
+        return Err("address has an empty component".to_owned());
+    }
+    match address.chars().find(|&c| {
+        matches!(c, ' ' | '#' | ',' | '?' | '*' | '[' | ']') || !c.is_ascii() || c.is_control()
+    }) {
+        Some(c @ ('?' | '*' | '[' | ']')) => Err(format!(
+            "pattern character {c:?} in the address; derived messages need a method address"
+        )),
+        Some(c) => Err(format!("invalid character {c:?} in the address")),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn to_message(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let mapping = Mapping::new(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let steps = mapping.address.iter().map(|part| match part {
+        Part::Literal(text) => quote!(address.push_str(#text);),
+        Part::Field(index) => {
+            let member = &mapping.fields[*index].member;
+            quote! {
+                ::osc_types10::__private::push_component(&mut address, &self.#member)?;
+            }
+        }
+    });
+    let args = mapping.args().map(|field| {
+        let Field { member, ty, .. } = field;
+        quote_spanned! {ty.span()=>
+            <#ty as ::osc_types10::OscArg>::to_osc_arg(&self.#member)
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::osc_types10::ToOscMessage for #name #ty_generics #where_clause {
+            fn to_osc_message(
+                &self,
+            ) -> ::core::result::Result<::osc_types10::MessageBuf, ::osc_types10::AddressError> {
+                let mut address = ::osc_types10::__private::String::new();
+                #(#steps)*
+                Ok(::osc_types10::MessageBuf::new(
+                    address,
+                    ::osc_types10::__private::vec![#(#args),*],
+                ))
+            }
+        }
+    })
+}
+
+pub(crate) fn from_message(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let mapping = Mapping::new(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mismatch = quote!(::osc_types10::ArgError::address_mismatch);
+
+    // Walk the address: strip each literal, and capture each field up to the
+    // next '/' (or the end).
+    let mut steps = Vec::new();
+    for part in &mapping.address {
+        match part {
+            Part::Literal(text) => steps.push(quote! {
+                let rest = rest.strip_prefix(#text).ok_or_else(#mismatch)?;
+            }),
+            Part::Field(index) => {
+                let field = &mapping.fields[*index];
+                let var = address_var(*index);
+                let ty = field.ty;
+                steps.push(quote_spanned! {ty.span()=>
+                    let (value, rest) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+                    if value.is_empty() {
+                        return Err(#mismatch());
+                    }
+                    let #var: #ty = ::core::str::FromStr::from_str(value).map_err(|_| #mismatch())?;
+                });
+            }
+        }
+    }
+
+    let arg_count = mapping.args().count();
+    let mut arg_index = 0usize;
+    let values = mapping.fields.iter().enumerate().map(|(index, field)| {
+        let Field { member, ty, .. } = field;
+        let value = if field.in_address {
+            let var = address_var(index);
+            quote!(#var)
+        } else {
+            let value = quote_spanned! {ty.span()=>
+                ::osc_types10::__private::arg::<#ty>(args, #arg_index)?
+            };
+            arg_index += 1;
+            value
+        };
+        match mapping.shape {
+            Shape::Named => quote!(#member: #value),
+            Shape::Tuple | Shape::Unit => value,
+        }
+    });
+    let construct = match mapping.shape {
+        Shape::Named => quote!(Self { #(#values),* }),
+        Shape::Tuple => quote!(Self ( #(#values),* )),
+        Shape::Unit => quote!(Self),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::osc_types10::FromOscMessage for #name #ty_generics #where_clause {
+            fn from_osc_message(
+                msg: &::osc_types10::Message<'_>,
+            ) -> ::core::result::Result<Self, ::osc_types10::ArgError> {
+                let rest = msg.address;
+                #(#steps)*
+                if !rest.is_empty() {
+                    return Err(#mismatch());
+                }
+                let args = &msg.args[..];
+                ::osc_types10::__private::no_extra(args, #arg_count)?;
+                Ok(#construct)
+            }
+        }
+    })
+}
+
+fn address_var(index: usize) -> Ident {
+    format_ident!("address_{}", index)
+}
@@ -0,0 +1,184 @@
+use osc_types10::{
+    osc, AddressErrorKind, ArgErrorKind, Color, FromOscMessage, Message, MessageBuf, OscArg,
+    OscType, OscTypeBuf, ToOscMessage,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, OscArg)]
+enum Mode {
+    Off,
+    On = 5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, OscArg)]
+#[osc(repr = "string")]
+enum Wave {
+    Sine,
+    #[osc(rename = "sq")]
+    Square,
+}
+
+#[derive(Debug, PartialEq, ToOscMessage, FromOscMessage)]
+#[osc(address = "/synth/{id}/params")]
+struct Params {
+    id: u32,
+    freq: f32,
+    name: String,
+    mode: Mode,
+    wave: Wave,
+    color: Color,
+    steps: Vec<i32>,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, ToOscMessage, FromOscMessage)]
+#[osc(address = "/mix/{1}/{0}")]
+struct Tuple(String, u8, f64);
+
+#[derive(Debug, PartialEq, ToOscMessage, FromOscMessage)]
+#[osc(address = "/point/x{x}/y{y}")]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, ToOscMessage, FromOscMessage)]
+#[osc(address = "/ping")]
+struct Ping;
+
+fn params() -> Params {
+    Params {
+        id: 7,
+        freq: 440.0,
+        name: "lead".into(),
+        mode: Mode::On,
+        wave: Wave::Square,
+        color: Color::new(1, 2, 3, 4),
+        steps: vec![1, 2],
+        data: vec![9],
+    }
+}
+
+#[test]
+fn maps_fields_in_order() {
+    let msg = params().to_osc_message().unwrap();
+    assert_eq!(
+        Message::from(&msg),
+        osc!(
+            "/synth/7/params",
+            440.0f32,
+            "lead",
+            5,
+            "sq",
+            Color::new(1, 2, 3, 4),
+            vec![OscType::Int(1), OscType::Int(2)],
+            &[9u8][..],
+        )
+    );
+    assert_eq!(Params::from_osc_message(&Message::from(&msg)), Ok(params()));
+}
+
+#[test]
+fn tuple_and_unit_structs() {
+    let value = Tuple("chorus".into(), 3, 0.5);
+    let msg = value.to_osc_message().unwrap();
+    assert_eq!(Message::from(&msg), osc!("/mix/3/chorus", 0.5));
+    assert_eq!(Tuple::from_osc_message(&Message::from(&msg)), Ok(value));
+
+    assert_eq!(Ping.to_osc_message(), Ok(MessageBuf::new("/ping", vec![])));
+    assert_eq!(Ping::from_osc_message(&osc!("/ping")), Ok(Ping));
+}
+
+#[test]
+fn fields_after_a_prefix_round_trip() {
+    let point = Point { x: -1, y: -2 };
+    let msg = point.to_osc_message().unwrap();
+    assert_eq!(msg.address, "/point/x-1/y-2");
+    assert_eq!(Point::from_osc_message(&Message::from(&msg)), Ok(point));
+    assert_eq!(
+        Point::from_osc_message(&osc!("/point/-1/y-2"))
+            .unwrap_err()
+            .kind(),
+        ArgErrorKind::AddressMismatch
+    );
+}
+
+#[test]
+fn address_fields_must_be_single_components() {
+    assert_eq!(
+        Tuple("a-b_c.1".into(), 2, 0.0)
+            .to_osc_message()
+            .unwrap()
+            .address,
+        "/mix/2/a-b_c.1"
+    );
+    let cases = [
+        ("a/b", AddressErrorKind::InvalidCharacter('/'), 8),
+        ("", AddressErrorKind::EmptyComponent, 7),
+        ("a b", AddressErrorKind::InvalidCharacter(' '), 8),
+        ("x*", AddressErrorKind::ReservedCharacter('*'), 8),
+        ("\u{e9}", AddressErrorKind::InvalidCharacter('\u{e9}'), 7),
+    ];
+    for (name, kind, position) in cases {
+        let err = Tuple(name.into(), 1, 0.0).to_osc_message().unwrap_err();
+        assert_eq!((err.kind(), err.position()), (kind, position), "{name:?}");
+    }
+}
+
+#[test]
+fn rejects_other_addresses() {
+    for address in [
+        "/synth//params",
+        "/synth/x/params",
+        "/synth/1/2/params",
+        "/synth/1/params/more",
+        "/other",
+    ] {
+        let mut msg = params().to_osc_message().unwrap();
+        msg.address = address.into();
+        let err = Params::from_osc_message(&Message::from(&msg)).unwrap_err();
+        assert_eq!(err.kind(), ArgErrorKind::AddressMismatch, "{address}");
+    }
+    assert_eq!(
+        Ping::from_osc_message(&osc!("/pingpong"))
+            .unwrap_err()
+            .kind(),
+        ArgErrorKind::AddressMismatch
+    );
+}
+
+#[test]
+fn reports_argument_errors() {
+    let err = Tuple::from_osc_message(&osc!("/mix/1/a", 1)).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        ArgErrorKind::WrongType {
+            expected: "d",
+            actual: 'i'
+        }
+    );
+    assert_eq!(err.index(), Some(0));
+
+    let err = Tuple::from_osc_message(&osc!("/mix/1/a")).unwrap_err();
+    assert_eq!(err.kind(), ArgErrorKind::Missing);
+
+    let err = Tuple::from_osc_message(&osc!("/mix/1/a", 1.0, 2.0)).unwrap_err();
+    assert_eq!(err.kind(), ArgErrorKind::Extra { actual: 'd' });
+    assert_eq!(err.index(), Some(1));
+}
+
+#[test]
+fn enums_reject_unknown_values() {
+    assert_eq!(Mode::from_osc_arg(&OscType::Int(0)), Ok(Mode::Off));
+    assert_eq!(Mode::Off.to_osc_arg(), OscTypeBuf::Int(0));
+    assert_eq!(
+        Mode::from_osc_arg(&OscType::Int(1)).unwrap_err().kind(),
+        ArgErrorKind::InvalidValue { actual: 'i' }
+    );
+    assert_eq!(Wave::from_osc_arg(&OscType::Symbol("Sine")), Ok(Wave::Sine));
+    assert_eq!(
+        Wave::from_osc_arg(&OscType::String("Square"))
+            .unwrap_err()
+            .kind(),
+        ArgErrorKind::InvalidValue { actual: 's' }
+    );
+}
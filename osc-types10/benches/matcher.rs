@@ -0,0 +1,68 @@
+//! Dispatching one incoming pattern against a few thousand registered addresses:
+//! the compiled `AddressMatcher` versus matching every route in turn.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use osc_types10::{matches_address, AddressMatcher};
+
+/// `/show/<group>/<fixture>/<param>` for 10 groups x 50 fixtures x 8 params = 4000 routes.
+fn routes() -> Vec<String> {
+    let params = [
+        "dimmer", "red", "green", "blue", "pan", "tilt", "zoom", "focus",
+    ];
+    let mut routes = Vec::new();
+    for group in 0..10 {
+        for fixture in 0..50 {
+            for param in params {
+                routes.push(format!("/show/group{group}/fixture{fixture}/{param}"));
+            }
+        }
+    }
+    routes
+}
+
+fn dispatch(c: &mut Criterion) {
+    let routes = routes();
+    let mut matcher = AddressMatcher::new();
+    for route in &routes {
+        matcher.insert(route).unwrap();
+    }
+
+    let patterns = [
+        ("literal", "/show/group7/fixture42/tilt"),
+        (
+            "alternatives",
+            "/show/group3/fixture{1,2,3}/{red,green,blue}",
+        ),
+        ("wildcard_leaf", "/show/group3/fixture12/*"),
+        ("wildcard_middle", "/show/group[0-4]/fixture?/dimmer"),
+        ("wildcard_all", "/show/*/*/pan"),
+    ];
+
+    let mut group = c.benchmark_group("dispatch_4000_routes");
+    for (name, pattern) in patterns {
+        group.bench_with_input(BenchmarkId::new("compiled", name), pattern, |b, p| {
+            let mut hits = Vec::new();
+            b.iter(|| {
+                hits.clear();
+                matcher.for_each_match(black_box(p), |id| hits.push(id));
+                hits.len()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("per_route", name), pattern, |b, p| {
+            let mut hits = Vec::new();
+            b.iter(|| {
+                hits.clear();
+                for (id, route) in routes.iter().enumerate() {
+                    if matches_address(black_box(p), route) {
+                        hits.push(id);
+                    }
+                }
+                hits.len()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dispatch);
+criterion_main!(benches);
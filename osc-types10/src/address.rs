@@ -0,0 +1,482 @@
+//! Validated OSC addresses and address patterns.
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{borrow::Cow, format, string::String};
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, format, string::String};
+
+use crate::pattern::{is_pattern_byte, validate_pattern, Pattern, PatternErrorKind};
+
+/// Reason a string is not a valid OSC address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressErrorKind {
+    /// The address does not start with `/`.
+    MissingLeadingSlash,
+    /// The address has an empty component, e.g. `/a//b`, `/a/` or `/`.
+    EmptyComponent,
+    /// A character that may not appear in an OSC address (space, `#`, `,`,
+    /// control characters or non-ASCII), or a `/` in a single component.
+    InvalidCharacter(char),
+    /// A pattern character (`?`, `*`, `[`, `]`, `{`, `}`) in an address that is
+    /// not a pattern.
+    ReservedCharacter(char),
+    /// The address is a malformed pattern.
+    Pattern(PatternErrorKind),
+}
+
+/// Error returned when validating an [`Address`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressError {
+    kind: AddressErrorKind,
+    position: usize,
+}
+
+impl AddressError {
+    /// What is wrong with the address.
+    pub fn kind(&self) -> AddressErrorKind {
+        self.kind
+    }
+
+    /// Byte offset in the address where the problem was detected.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            AddressErrorKind::MissingLeadingSlash => f.write_str("address must start with '/'")?,
+            AddressErrorKind::EmptyComponent => f.write_str("empty address component")?,
+            AddressErrorKind::InvalidCharacter(c) => write!(f, "invalid character {c:?}")?,
+            AddressErrorKind::ReservedCharacter(c) => {
+                write!(f, "pattern character {c:?} in a non-pattern address")?
+            }
+            AddressErrorKind::Pattern(kind) => write!(f, "malformed pattern: {kind}")?,
+        }
+        write!(f, " at byte {}", self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AddressError {}
+
+/// A validated OSC address, either borrowed or owned.
+///
+/// A method address must start with `/`, have no empty components and contain
+/// only printable ASCII other than space, `#` and `,`. The pattern characters
+/// `?`, `*`, `[`, `]`, `{` and `}` are only accepted when the address is created
+/// as a pattern with [`Address::pattern`].
+///
+/// ```
+/// use osc_types10::Address;
+///
+/// let address = Address::new("/synth/1/freq").unwrap();
+/// assert!(address.components().eq(["synth", "1", "freq"]));
+/// assert_eq!(address.parent().unwrap(), "/synth/1");
+/// assert_eq!(address.parent().unwrap().child("amp").unwrap(), "/synth/1/amp");
+///
+/// assert!(Address::new("foo bar").is_err());
+/// assert!(Address::new("/synth/*").is_err());
+/// assert!(Address::pattern("/synth/*").unwrap().matches("/synth/1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address<'a> {
+    inner: Cow<'a, str>,
+    pattern: bool,
+}
+
+impl<'a> Address<'a> {
+    /// Validate and borrow a method address.
+    pub fn new(address: &'a str) -> Result<Self, AddressError> {
+        validate_address(address)?;
+        Ok(Self {
+            inner: Cow::Borrowed(address),
+            pattern: false,
+        })
+    }
+
+    /// Validate and borrow an address pattern.
+    ///
+    /// See [`Pattern`] for the supported syntax.
+    pub fn pattern(pattern: &'a str) -> Result<Self, AddressError> {
+        validate_address_pattern(pattern)?;
+        Ok(Self {
+            inner: Cow::Borrowed(pattern),
+            pattern: true,
+        })
+    }
+
+    /// The address string.
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    /// Returns `true` if this address was created as a pattern.
+    pub fn is_pattern(&self) -> bool {
+        self.pattern
+    }
+
+    /// Returns `true` if the string is borrowed rather than owned.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.inner, Cow::Borrowed(_))
+    }
+
+    /// Iterate over the `/`-separated components, without the leading empty one.
+    pub fn components(&self) -> core::str::Split<'_, char> {
+        let mut components = self.inner.split('/');
+        components.next();
+        components
+    }
+
+    /// Number of components.
+    pub fn depth(&self) -> usize {
+        self.components().count()
+    }
+
+    /// The last component.
+    pub fn name(&self) -> &str {
+        let start = self.inner.rfind('/').map_or(0, |i| i + 1);
+        &self.inner[start..]
+    }
+
+    /// The address without its last component, or `None` for a top-level address.
+    ///
+    /// The parent of a borrowed address borrows the same string.
+    pub fn parent(&self) -> Option<Address<'a>> {
+        let end = self.inner.rfind('/').filter(|&i| i > 0)?;
+        let inner = match &self.inner {
+            Cow::Borrowed(s) => Cow::Borrowed(&s[..end]),
+            Cow::Owned(s) => Cow::Owned(String::from(&s[..end])),
+        };
+        Some(Address {
+            inner,
+            pattern: self.pattern,
+        })
+    }
+
+    /// Append a single component.
+    ///
+    /// A `/` in `name` is reported as [`AddressErrorKind::InvalidCharacter`];
+    /// use [`Address::join`] to append several components.
+    pub fn child(&self, name: &str) -> Result<Address<'static>, AddressError> {
+        if let Some(i) = name.find('/') {
+            return Err(AddressError {
+                kind: AddressErrorKind::InvalidCharacter('/'),
+                position: self.inner.len() + 1 + i,
+            });
+        }
+        self.join(name)
+    }
+
+    /// Append one or more `/`-separated components, e.g. `join("1/freq")`.
+    ///
+    /// The result is a pattern if this address is one.
+    pub fn join(&self, relative: &str) -> Result<Address<'static>, AddressError> {
+        let mut joined = String::with_capacity(self.inner.len() + 1 + relative.len());
+        joined.push_str(&self.inner);
+        joined.push('/');
+        joined.push_str(relative);
+        if self.pattern {
+            Address::pattern_from_string(joined)
+        } else {
+            Address::from_string(joined)
+        }
+    }
+
+    /// Returns `true` if this address, read as a pattern, matches `address`.
+    ///
+    /// A method address only matches itself.
+    pub fn matches(&self, address: &str) -> bool {
+        if self.pattern {
+            Pattern::new(&self.inner).is_ok_and(|p| p.matches(address))
+        } else {
+            *self.inner == *address
+        }
+    }
+
+    /// A borrowed view of this address.
+    pub fn borrowed(&self) -> Address<'_> {
+        Address {
+            inner: Cow::Borrowed(&self.inner),
+            pattern: self.pattern,
+        }
+    }
+
+    /// Convert into an address that owns its string.
+    pub fn into_owned(self) -> Address<'static> {
+        Address {
+            inner: Cow::Owned(self.inner.into_owned()),
+            pattern: self.pattern,
+        }
+    }
+
+    /// Convert into the underlying string.
+    pub fn into_string(self) -> String {
+        self.inner.into_owned()
+    }
+}
+
+impl Address<'static> {
+    /// Validate and take ownership of a method address.
+    pub fn from_string(address: String) -> Result<Self, AddressError> {
+        validate_address(&address)?;
+        Ok(Self {
+            inner: Cow::Owned(address),
+            pattern: false,
+        })
+    }
+
+    /// Validate and take ownership of an address pattern.
+    pub fn pattern_from_string(pattern: String) -> Result<Self, AddressError> {
+        validate_address_pattern(&pattern)?;
+        Ok(Self {
+            inner: Cow::Owned(pattern),
+            pattern: true,
+        })
+    }
+}
+
+impl AsRef<str> for Address<'_> {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl core::ops::Deref for Address<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.inner
+    }
+}
+
+impl fmt::Display for Address<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl PartialEq<str> for Address<'_> {
+    fn eq(&self, other: &str) -> bool {
+        *self.inner == *other
+    }
+}
+
+impl PartialEq<&str> for Address<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        *self.inner == **other
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Address<'a> {
+    type Error = AddressError;
+
+    fn try_from(address: &'a str) -> Result<Self, AddressError> {
+        Address::new(address)
+    }
+}
+
+impl TryFrom<String> for Address<'static> {
+    type Error = AddressError;
+
+    fn try_from(address: String) -> Result<Self, AddressError> {
+        Address::from_string(address)
+    }
+}
+
+impl<'a> From<Address<'a>> for String {
+    fn from(address: Address<'a>) -> Self {
+        address.into_string()
+    }
+}
+
+/// Check that `address` is a valid OSC method address (no pattern characters).
+pub fn validate_address(address: &str) -> Result<(), AddressError> {
+    check_structure(address, false)
+}
+
+/// Check that `pattern` is a valid OSC address pattern.
+pub(crate) fn validate_address_pattern(pattern: &str) -> Result<(), AddressError> {
+    check_structure(pattern, true)?;
+    validate_pattern(pattern).map_err(|e| AddressError {
+        kind: AddressErrorKind::Pattern(e.kind()),
+        position: e.position(),
+    })
+}
+
+/// Check that `address[start..]`, appended to an address being built, is a
+/// single non-empty method address component.
+pub(crate) fn check_component(address: &str, start: usize) -> Result<(), AddressError> {
+    let component = &address[start..];
+    if component.is_empty() {
+        return Err(AddressError {
+            kind: AddressErrorKind::EmptyComponent,
+            position: start,
+        });
+    }
+    if let Some(i) = component.find('/') {
+        return Err(AddressError {
+            kind: AddressErrorKind::InvalidCharacter('/'),
+            position: start + i,
+        });
+    }
+    check_structure(&format!("/{component}"), false).map_err(|e| AddressError {
+        position: start + e.position - 1,
+        ..e
+    })
+}
+
+fn check_structure(address: &str, pattern: bool) -> Result<(), AddressError> {
+    let err = |kind, position| Err(AddressError { kind, position });
+    if !address.starts_with('/') {
+        return err(AddressErrorKind::MissingLeadingSlash, 0);
+    }
+    let mut in_braces = false;
+    for (pos, b) in address.bytes().enumerate() {
+        match b {
+            b'/' if address.as_bytes().get(pos + 1).map_or(true, |&n| n == b'/') => {
+                return err(AddressErrorKind::EmptyComponent, pos + 1);
+            }
+            b'{' => in_braces = true,
+            b'}' => in_braces = false,
+            b',' if pattern && in_braces => {}
+            b' ' | b'#' | b',' | 0x00..=0x1f | 0x7f..=0xff => {
+                let c = address[pos..].chars().next().unwrap_or('\0');
+                return err(AddressErrorKind::InvalidCharacter(c), pos);
+            }
+            _ => {}
+        }
+        if !pattern && is_pattern_byte(b) {
+            return err(AddressErrorKind::ReservedCharacter(b as char), pos);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_address, Address, AddressErrorKind};
+    use crate::PatternErrorKind;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
+
+    #[test]
+    fn accepts_valid_addresses() {
+        for address in ["/a", "/synth/1/freq", "/with-dash_and.dot/x~y"] {
+            let parsed = Address::new(address).unwrap();
+            assert_eq!(parsed, address);
+            assert!(!parsed.is_pattern());
+            assert!(parsed.is_borrowed());
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        let cases = [
+            ("", AddressErrorKind::MissingLeadingSlash, 0),
+            ("foo bar", AddressErrorKind::MissingLeadingSlash, 0),
+            ("/", AddressErrorKind::EmptyComponent, 1),
+            ("/a//b", AddressErrorKind::EmptyComponent, 3),
+            ("/a/", AddressErrorKind::EmptyComponent, 3),
+            ("/foo bar", AddressErrorKind::InvalidCharacter(' '), 4),
+            ("/a#b", AddressErrorKind::InvalidCharacter('#'), 2),
+            ("/a,b", AddressErrorKind::InvalidCharacter(','), 2),
+            (
+                "/caf\u{e9}",
+                AddressErrorKind::InvalidCharacter('\u{e9}'),
+                4,
+            ),
+            ("/a/*", AddressErrorKind::ReservedCharacter('*'), 3),
+            ("/a[1]", AddressErrorKind::ReservedCharacter('['), 2),
+        ];
+        for (address, kind, position) in cases {
+            let err = validate_address(address).unwrap_err();
+            assert_eq!((err.kind(), err.position()), (kind, position), "{address}");
+        }
+    }
+
+    #[test]
+    fn patterns_are_validated_separately() {
+        let pattern = Address::pattern("/synth/{1,2}/*").unwrap();
+        assert!(pattern.is_pattern());
+        assert!(pattern.matches("/synth/2/freq"));
+        assert!(!pattern.matches("/synth/3/freq"));
+
+        let err = Address::pattern("/synth/[1").unwrap_err();
+        assert_eq!(
+            err.kind(),
+            AddressErrorKind::Pattern(PatternErrorKind::UnclosedBracket)
+        );
+        assert_eq!(
+            Address::pattern("/a b").unwrap_err().kind(),
+            AddressErrorKind::InvalidCharacter(' ')
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_display_is_descriptive() {
+        let err = Address::pattern("/a{b").unwrap_err();
+        assert_eq!(err.to_string(), "malformed pattern: unclosed '{' at byte 2");
+    }
+
+    #[test]
+    fn components_parent_and_name() {
+        let address = Address::new("/synth/1/freq").unwrap();
+
+        assert!(address.components().eq(["synth", "1", "freq"]));
+        assert_eq!(address.depth(), 3);
+        assert_eq!(address.name(), "freq");
+
+        let parent = address.parent().unwrap();
+        assert_eq!(parent, "/synth/1");
+        assert!(parent.is_borrowed());
+        assert_eq!(parent.parent().unwrap(), "/synth");
+        assert!(parent.parent().unwrap().parent().is_none());
+    }
+
+    #[test]
+    fn child_and_join_validate_the_result() {
+        let synth = Address::new("/synth").unwrap();
+
+        assert_eq!(synth.child("1").unwrap(), "/synth/1");
+        assert_eq!(synth.join("1/freq").unwrap(), "/synth/1/freq");
+        let err = synth.child("1/freq").unwrap_err();
+        assert_eq!(err.kind(), AddressErrorKind::InvalidCharacter('/'));
+        assert_eq!(err.position(), 8);
+        assert_eq!(
+            synth.child("/").unwrap_err().kind(),
+            AddressErrorKind::InvalidCharacter('/')
+        );
+        assert_eq!(
+            synth.child("").unwrap_err().kind(),
+            AddressErrorKind::EmptyComponent
+        );
+        assert_eq!(
+            synth.child("*").unwrap_err().kind(),
+            AddressErrorKind::ReservedCharacter('*')
+        );
+
+        let pattern = Address::pattern("/synth").unwrap();
+        assert!(pattern.child("*").unwrap().is_pattern());
+    }
+
+    #[test]
+    fn borrowing_and_owning() {
+        let owned = Address::from_string(String::from("/owned/path")).unwrap();
+        assert!(!owned.is_borrowed());
+        assert!(owned.borrowed().is_borrowed());
+        assert_eq!(owned.parent().unwrap(), "/owned");
+
+        let borrowed = Address::new("/borrowed").unwrap();
+        let owned: Address<'static> = borrowed.clone().into_owned();
+        assert_eq!(owned, borrowed);
+        assert_eq!(owned.into_string(), "/borrowed");
+        assert!(Address::try_from("nope").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Conversions between [`OscType`] and Rust values.
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{Bundle, Color, Message, MidiMessage, OscPacket, OscType, TimeTag};
+
+/// Reason an argument could not be converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgErrorKind {
+    /// The argument has a different type.
+    ///
+    /// `expected` lists the accepted type tags, e.g. `"TF"` for `bool`.
+    WrongType {
+        /// Type tags the requested Rust type converts from.
+        expected: &'static str,
+        /// Type tag of the argument.
+        actual: char,
+    },
+    /// The message has no argument at this index.
+    Missing,
+    /// The message has more arguments than requested.
+    Extra {
+        /// Type tag of the first surplus argument.
+        actual: char,
+    },
+    /// The argument has the right type but a value the Rust type cannot
+    /// represent, e.g. an unknown enum variant.
+    InvalidValue {
+        /// Type tag of the argument.
+        actual: char,
+    },
+    /// The address does not match the one the Rust type expects.
+    AddressMismatch,
+}
+
+/// Error returned when converting an [`OscType`] to a Rust value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgError {
+    kind: ArgErrorKind,
+    index: Option<usize>,
+}
+
+impl ArgError {
+    fn wrong_type(expected: &'static str, arg: &OscType<'_>) -> Self {
+        Self {
+            kind: ArgErrorKind::WrongType {
+                expected,
+                actual: arg.tag(),
+            },
+            index: None,
+        }
+    }
+
+    /// An [`ArgErrorKind::InvalidValue`] error for `arg`.
+    pub fn invalid_value(arg: &OscType<'_>) -> Self {
+        Self {
+            kind: ArgErrorKind::InvalidValue { actual: arg.tag() },
+            index: None,
+        }
+    }
+
+    /// An [`ArgErrorKind::AddressMismatch`] error.
+    pub fn address_mismatch() -> Self {
+        Self {
+            kind: ArgErrorKind::AddressMismatch,
+            index: None,
+        }
+    }
+
+    pub(crate) fn missing(index: usize) -> Self {
+        Self {
+            kind: ArgErrorKind::Missing,
+            index: Some(index),
+        }
+    }
+
+    pub(crate) fn at(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> ArgErrorKind {
+        self.kind
+    }
+
+    /// Index of the offending argument, if the error came from a message.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ArgErrorKind::WrongType { expected, actual } => {
+                f.write_str("expected type tag ")?;
+                for (i, tag) in expected.chars().enumerate() {
+                    if i > 0 {
+                        f.write_str(" or ")?;
+                    }
+                    write!(f, "{tag:?}")?;
+                }
+                write!(f, ", found {actual:?}")?;
+            }
+            ArgErrorKind::Missing => f.write_str("missing argument")?,
+            ArgErrorKind::Extra { actual } => write!(f, "unexpected argument {actual:?}")?,
+            ArgErrorKind::InvalidValue { actual } => {
+                write!(f, "invalid value for type tag {actual:?}")?
+            }
+            ArgErrorKind::AddressMismatch => f.write_str("address does not match")?,
+        }
+        match self.index {
+            Some(index) => write!(f, " at argument {index}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArgError {}
+
+/// Conversions for the types that map to exactly one variant.
+macro_rules! conversions {
+    ($($ty:ty => $variant:ident, $tags:literal;)*) => {$(
+        impl<'a> From<$ty> for OscType<'a> {
+            fn from(value: $ty) -> Self {
+                OscType::$variant(value)
+            }
+        }
+
+        impl<'a> TryFrom<&OscType<'a>> for $ty {
+            type Error = ArgError;
+
+            fn try_from(arg: &OscType<'a>) -> Result<Self, ArgError> {
+                match *arg {
+                    OscType::$variant(value) => Ok(value),
+                    _ => Err(ArgError::wrong_type($tags, arg)),
+                }
+            }
+        }
+    )*};
+}
+
+conversions! {
+    i32 => Int, "i";
+    f32 => Float, "f";
+    &'a [u8] => Blob, "b";
+    i64 => Long, "h";
+    TimeTag => Time, "t";
+    f64 => Double, "d";
+    char => Char, "c";
+    Color => Color, "r";
+    MidiMessage => Midi, "m";
+    bool => Bool, "TF";
+}
+
+/// Strings convert to [`OscType::String`]; both strings and symbols convert back.
+impl<'a> From<&'a str> for OscType<'a> {
+    fn from(value: &'a str) -> Self {
+        OscType::String(value)
+    }
+}
+
+impl<'a> TryFrom<&OscType<'a>> for &'a str {
+    type Error = ArgError;
+
+    fn try_from(arg: &OscType<'a>) -> Result<Self, ArgError> {
+        match *arg {
+            OscType::String(s) | OscType::Symbol(s) => Ok(s),
+            _ => Err(ArgError::wrong_type("sS", arg)),
+        }
+    }
+}
+
+impl<'a> From<Vec<OscType<'a>>> for OscType<'a> {
+    fn from(items: Vec<OscType<'a>>) -> Self {
+        OscType::Array(items)
+    }
+}
+
+impl<'b, 'a> TryFrom<&'b OscType<'a>> for &'b [OscType<'a>] {
+    type Error = ArgError;
+
+    fn try_from(arg: &'b OscType<'a>) -> Result<Self, ArgError> {
+        match arg {
+            OscType::Array(items) => Ok(items),
+            _ => Err(ArgError::wrong_type("[", arg)),
+        }
+    }
+}
+
+impl<'a> From<Message<'a>> for OscPacket<'a> {
+    fn from(msg: Message<'a>) -> Self {
+        OscPacket::Message(msg)
+    }
+}
+
+impl<'a> From<Bundle<'a>> for OscPacket<'a> {
+    fn from(bundle: Bundle<'a>) -> Self {
+        OscPacket::Bundle(bundle)
+    }
+}
+
+/// Convert one argument, recording its index in the error.
+pub(crate) fn arg<'b, 'a, T>(args: &'b [OscType<'a>], index: usize) -> Result<T, ArgError>
+where
+    T: TryFrom<&'b OscType<'a>, Error = ArgError>,
+{
+    let arg = args.get(index).ok_or(ArgError::missing(index))?;
+    T::try_from(arg).map_err(|e| e.at(index))
+}
+
+/// Fail if `args` has more than `len` elements.
+pub(crate) fn no_extra(args: &[OscType<'_>], len: usize) -> Result<(), ArgError> {
+    match args.get(len) {
+        Some(extra) => Err(ArgError {
+            kind: ArgErrorKind::Extra {
+                actual: extra.tag(),
+            },
+            index: Some(len),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Tuples a whole argument list converts to; see
+/// [`Message::args_as`](crate::Message::args_as).
+pub trait FromArgs<'b, 'a: 'b>: Sized {
+    /// Convert `args`, which must have exactly one element per tuple field.
+    fn from_args(args: &'b [OscType<'a>]) -> Result<Self, ArgError>;
+}
+
+macro_rules! tuples {
+    ($($len:literal => ($($t:ident $i:tt),*);)*) => {$(
+        impl<'b, 'a: 'b, $($t),*> FromArgs<'b, 'a> for ($($t,)*)
+        where
+            $($t: TryFrom<&'b OscType<'a>, Error = ArgError>,)*
+        {
+            fn from_args(args: &'b [OscType<'a>]) -> Result<Self, ArgError> {
+                no_extra(args, $len)?;
+                Ok(($(arg::<$t>(args, $i)?,)*))
+            }
+        }
+    )*};
+}
+
+tuples! {
+    0 => ();
+    1 => (A 0);
+    2 => (A 0, B 1);
+    3 => (A 0, B 1, C 2);
+    4 => (A 0, B 1, C 2, D 3);
+    5 => (A 0, B 1, C 2, D 3, E 4);
+    6 => (A 0, B 1, C 2, D 3, E 4, F 5);
+    7 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+    8 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArgError, ArgErrorKind};
+    use crate::{Color, Message, OscType, TimeTag};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn converts_both_ways() {
+        let args: [OscType<'_>; 5] = [
+            1i32.into(),
+            0.5f32.into(),
+            "s".into(),
+            TimeTag::from(7).into(),
+            Color::new(1, 2, 3, 4).into(),
+        ];
+        assert_eq!(i32::try_from(&args[0]), Ok(1));
+        assert_eq!(f32::try_from(&args[1]), Ok(0.5));
+        assert_eq!(<&str>::try_from(&args[2]), Ok("s"));
+        assert_eq!(<&str>::try_from(&OscType::Symbol("sym")), Ok("sym"));
+        assert_eq!(TimeTag::try_from(&args[3]), Ok(TimeTag::from(7)));
+        assert_eq!(Color::try_from(&args[4]), Ok(Color::new(1, 2, 3, 4)));
+        assert_eq!(
+            OscType::from(vec![OscType::Nil]),
+            OscType::Array(vec![OscType::Nil])
+        );
+        assert_eq!(bool::try_from(&OscType::Bool(false)), Ok(false));
+    }
+
+    #[test]
+    fn wrong_type_names_both_tags() {
+        let err = f32::try_from(&OscType::Int(1)).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ArgErrorKind::WrongType {
+                expected: "f",
+                actual: 'i'
+            }
+        );
+        assert_eq!(err.index(), None);
+        assert_eq!(err.to_string(), "expected type tag 'f', found 'i'");
+
+        let err = bool::try_from(&OscType::Nil).unwrap_err();
+        assert_eq!(err.to_string(), "expected type tag 'T' or 'F', found 'N'");
+    }
+
+    #[test]
+    fn message_accessors() {
+        let msg = Message::new(
+            "/a",
+            vec![OscType::Int(1), OscType::Float(2.0), OscType::String("x")],
+        );
+        assert_eq!(msg.arg::<f32>(1), Ok(2.0));
+        assert_eq!(msg.args_as::<(i32, f32, &str)>(), Ok((1, 2.0, "x")));
+
+        let err = msg.arg::<i64>(0).unwrap_err();
+        assert_eq!(err.index(), Some(0));
+        assert_eq!(
+            err.to_string(),
+            "expected type tag 'h', found 'i' at argument 0"
+        );
+
+        let err = msg.arg::<i32>(3).unwrap_err();
+        assert_eq!(err.kind(), ArgErrorKind::Missing);
+        assert_eq!(err.index(), Some(3));
+
+        let err: ArgError = msg.args_as::<(i32, f32)>().unwrap_err();
+        assert_eq!(err.kind(), ArgErrorKind::Extra { actual: 's' });
+        assert_eq!(err.index(), Some(2));
+
+        let err = msg.args_as::<(i32, f32, &str, bool)>().unwrap_err();
+        assert_eq!(err.kind(), ArgErrorKind::Missing);
+        assert_eq!(err.index(), Some(3));
+
+        let err = msg.args_as::<(i32, &str, &str)>().unwrap_err();
+        assert_eq!(err.index(), Some(1));
+    }
+
+    #[test]
+    fn arrays_borrow_their_items() {
+        let msg = Message::new("/a", vec![vec![OscType::Int(1), OscType::Int(2)].into()]);
+        let items = msg.arg::<&[OscType<'_>]>(0).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(msg.args_as::<(i32,)>().unwrap_err().index(), Some(0));
+    }
+}
@@ -2,42 +2,163 @@
 #![deny(missing_docs, unreachable_pub, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(not(feature = "std"), no_std)]
-#![doc = r#"
+#![doc = r##"
 # osc-types10
 **⚠ Experimental / Not for production use**
 
 Defines message and bundle types for Open Sound Control 1.0.
 - Pre-release (`0.1.0-alpha`)
 - Breaking changes may occur frequently
-- `no_std` compatible (optional)
+- `no_std` compatible (optional), with or without `alloc`
 
 ## Example
 ```rust
+# #[cfg(feature = "alloc")] {
 use osc_types10::{Message, OscType};
 let msg = Message::new("/example", vec![OscType::String("abc")]);
 println!("{msg:?}");
+# }
 ```
-"#]
 
-#[cfg(not(feature = "std"))]
+## Features
+- `std` (default): `std::error::Error` impls and `SystemTime` conversions. Implies `alloc`.
+- `alloc`: messages, bundles, arguments, addresses and the address-space types,
+  all of which hold `Vec`s or `String`s.
+- `derive`: `#[derive(ToOscMessage, FromOscMessage, OscArg)]` from the
+  `osc-derive` crate. Implies `alloc`.
+- `serde`: `Serialize` and `Deserialize` for packets, messages, bundles and
+  arguments, borrowed and owned. Implies `alloc`; see [below](#serde).
+
+Without `alloc` only [`TimeTag`], [`Color`], [`MidiMessage`] and pattern
+matching are available.
+
+## Text syntax
+Messages, bundles and packets implement `Display`, and their owned
+counterparts `FromStr`, for a compact text form that round-trips exactly:
+
+```rust
+# #[cfg(feature = "alloc")] {
+use osc_types10::{osc, OscPacketBuf};
+
+let text = "#bundle 1 [ /synth/freq ,fi 440.0 1 /note ,s[ih] \"on\" [60 0] ]";
+let packet: OscPacketBuf = text.parse().unwrap();
+assert_eq!(packet.to_string(), text);
+assert_eq!(osc!("/synth/freq", 440.0f32, 1).to_string(), "/synth/freq ,fi 440.0 1");
+# }
+```
+
+A message is its address, then, if it has arguments, the type tag string and
+the values separated by whitespace. `T`, `F`, `N` and `I` have no value, and
+array values are enclosed in `[...]`. The values are written as follows:
+- `i`, `h`: decimal integers
+- `f`, `d`: the shortest decimal that parses back to the same value, `inf`,
+  `-inf`, `NaN`, or `NaN:0x...` with the bits of any other NaN
+- `s`, `S`: double-quoted strings with the escapes `\\`, `\"`, `\n`, `\r`,
+  `\t` and `\u{...}`
+- `b`: `0x` followed by two hex digits per byte
+- `t`: the 64-bit time tag as a decimal integer (`1` is immediately)
+- `c`: a single-quoted character
+- `r`: `#rrggbbaa` in hex
+- `m`: `port:status:data1:data2`, each byte as two hex digits
+
+Addresses that are empty or contain whitespace, quotes or non-ASCII
+characters, or that start with `#`, `,` or `]`, are double-quoted.
+A bundle is `#bundle`, its time tag and its elements in `[ ... ]`; the
+alternate form (`{:#}`) puts each element on its own indented line.
+
+## Serde
+With the `serde` feature the types use this representation:
+- `OscType` and `OscTypeBuf` are externally tagged with their type tag,
+  e.g. `{"i": 1}`, `{"s": "x"}` and `{"[": [{"f": 0.5}]}`; the data-less
+  `T`, `F`, `N` and `I` are the bare strings `"T"`, `"F"`, `"N"` and `"I"`.
+- Blobs are base64 strings (standard alphabet, padded) in human-readable
+  formats and byte arrays otherwise.
+- [`TimeTag`]s are RFC 3339 UTC strings such as
+  `"2024-05-01T12:00:00.5Z"`, or `"immediately"`, in human-readable formats
+  and their 64-bit wire value otherwise. The fractional seconds have up to ten
+  digits, enough to round-trip the 32-bit NTP fraction exactly. The wire value
+  is wrapped in a newtype struct named `TimeTag`, so formats can recognize it.
+- [`Color`] and [`MidiMessage`] are structs with their field names.
+- `Message` is `{"address": ..., "args": [...]}`, `Bundle` is
+  `{"timetag": ..., "packets": [...]}` and `OscPacket` is `{"message": ...}`
+  or `{"bundle": ...}`; the owned types match.
+
+The borrowed types borrow strings and blobs from the input. Formats that
+cannot lend them, such as JSON strings with escapes or base64 blobs, fail to
+deserialize into them; use the owned types for those.
+"##]
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod address;
+#[cfg(feature = "alloc")]
+mod convert;
+#[cfg(feature = "alloc")]
+mod macros;
+#[cfg(feature = "alloc")]
+mod mapping;
+#[cfg(feature = "alloc")]
+mod matcher;
+#[cfg(feature = "alloc")]
+mod owned;
+mod pattern;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "alloc")]
+mod space;
+#[cfg(feature = "alloc")]
+mod text;
+mod timetag;
+
+#[cfg(feature = "alloc")]
+pub use address::{validate_address, Address, AddressError, AddressErrorKind};
+#[cfg(feature = "alloc")]
+pub use convert::{ArgError, ArgErrorKind, FromArgs};
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use macros::__private;
+#[cfg(feature = "alloc")]
+pub use mapping::{FromOscMessage, OscArg, ToOscMessage};
+#[cfg(feature = "alloc")]
+pub use matcher::AddressMatcher;
+#[cfg(feature = "derive")]
+pub use osc_derive::{FromOscMessage, OscArg, ToOscMessage};
+#[cfg(feature = "alloc")]
+pub use owned::{AsOscType, BundleBuf, MessageBuf, OscPacketBuf, OscTypeBuf};
+pub use pattern::{matches_address, validate_pattern, Pattern, PatternError, PatternErrorKind};
+#[cfg(feature = "alloc")]
+pub use space::{AddressSpace, AddressSpaceIter, AddressSpaceQuery};
+#[cfg(feature = "alloc")]
+pub use text::{ParseError, ParseErrorKind};
+pub use timetag::TimeTag;
+
 #[cfg(feature = "std")]
 use std::vec::Vec;
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 use alloc::vec::Vec;
 
 /// OSC packet - either a message or a bundle
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum OscPacket<'a> {
     /// An OSC message
+    #[cfg_attr(feature = "serde", serde(borrow))]
     Message(Message<'a>),
     /// An OSC bundle
+    #[cfg_attr(feature = "serde", serde(borrow))]
     Bundle(Bundle<'a>),
 }
 
 /// OSC argument types as defined in OSC 1.0 specification
+///
+/// `Int`, `Float`, `String` and `Blob` are the required argument types; the
+/// remaining variants cover the optional types listed by the specification.
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq)]
 pub enum OscType<'a> {
     /// 32-bit integer (i)
@@ -48,56 +169,225 @@ pub enum OscType<'a> {
     String(&'a str),
     /// Binary blob (b)
     Blob(&'a [u8]),
+    /// 64-bit integer (h)
+    Long(i64),
+    /// OSC time tag (t)
+    Time(TimeTag),
+    /// 64-bit IEEE 754 double (d)
+    Double(f64),
+    /// Alternate string type, e.g. for symbols (S)
+    Symbol(&'a str),
+    /// ASCII character sent as 32 bits (c)
+    Char(char),
+    /// 32-bit RGBA color (r)
+    Color(Color),
+    /// 4-byte MIDI message (m)
+    Midi(MidiMessage),
+    /// Boolean carried by the type tag alone (T or F)
+    Bool(bool),
+    /// Nil, carries no data (N)
+    Nil,
+    /// Infinitum, carries no data (I)
+    Inf,
+    /// Array of arguments, delimited by `[` and `]` in the type tag string
+    ///
+    /// Arrays may be nested arbitrarily.
+    Array(Vec<OscType<'a>>),
+}
+
+#[cfg(feature = "alloc")]
+impl OscType<'_> {
+    /// Returns the type tag character used for this argument in a type tag string.
+    ///
+    /// For [`OscType::Array`] this is the opening `[`; the array's own tags and the
+    /// closing `]` follow it in the type tag string.
+    pub fn tag(&self) -> char {
+        match self {
+            OscType::Int(_) => 'i',
+            OscType::Float(_) => 'f',
+            OscType::String(_) => 's',
+            OscType::Blob(_) => 'b',
+            OscType::Long(_) => 'h',
+            OscType::Time(_) => 't',
+            OscType::Double(_) => 'd',
+            OscType::Symbol(_) => 'S',
+            OscType::Char(_) => 'c',
+            OscType::Color(_) => 'r',
+            OscType::Midi(_) => 'm',
+            OscType::Bool(true) => 'T',
+            OscType::Bool(false) => 'F',
+            OscType::Nil => 'N',
+            OscType::Inf => 'I',
+            OscType::Array(_) => '[',
+        }
+    }
+}
+
+/// 32-bit RGBA color argument (r)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    /// Red component
+    pub red: u8,
+    /// Green component
+    pub green: u8,
+    /// Blue component
+    pub blue: u8,
+    /// Alpha component
+    pub alpha: u8,
+}
+
+impl Color {
+    /// Create a new color from its components
+    pub const fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+/// 4-byte MIDI message argument (m)
+///
+/// Bytes from MSB to LSB are: port id, status byte, data1, data2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MidiMessage {
+    /// MIDI port id
+    pub port: u8,
+    /// Status byte
+    pub status: u8,
+    /// First data byte
+    pub data1: u8,
+    /// Second data byte
+    pub data2: u8,
+}
+
+impl MidiMessage {
+    /// Create a new MIDI message from its bytes
+    pub const fn new(port: u8, status: u8, data1: u8, data2: u8) -> Self {
+        Self {
+            port,
+            status,
+            data1,
+            data2,
+        }
+    }
 }
 
 /// OSC Message as defined in OSC 1.0 specification
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Message<'a> {
     /// OSC address pattern
     pub address: &'a str,
     /// Arguments of the message
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub args: Vec<OscType<'a>>,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> Message<'a> {
     /// Create a new OSC message
     pub fn new(address: &'a str, args: Vec<OscType<'a>>) -> Self {
         Self { address, args }
     }
 
+    /// Create a new OSC message, checking that `address` is a well-formed address pattern
+    pub fn try_new(address: &'a str, args: Vec<OscType<'a>>) -> Result<Self, AddressError> {
+        address::validate_address_pattern(address)?;
+        Ok(Self::new(address, args))
+    }
+
+    /// The message's address as a validated [`Address`] pattern
+    pub fn address_pattern(&self) -> Result<Address<'a>, AddressError> {
+        Address::pattern(self.address)
+    }
+
     /// Create a new OSC message with string arguments (convenience method)
     pub fn with_strings(address: &'a str, string_args: Vec<&'a str>) -> Self {
         let args = string_args.into_iter().map(OscType::String).collect();
         Self::new(address, args)
     }
+
+    /// Returns `true` if this message's address pattern matches the method `address`.
+    ///
+    /// See [`Pattern`] for the supported syntax.
+    pub fn matches(&self, address: &str) -> bool {
+        matches_address(self.address, address)
+    }
+
+    /// Convert the argument at `index` to `T`.
+    ///
+    /// ```
+    /// use osc_types10::{Message, OscType};
+    ///
+    /// let msg = Message::new("/synth/freq", vec![OscType::Float(440.0)]);
+    /// assert_eq!(msg.arg::<f32>(0), Ok(440.0));
+    /// assert!(msg.arg::<i32>(0).is_err());
+    /// ```
+    pub fn arg<'b, T>(&'b self, index: usize) -> Result<T, ArgError>
+    where
+        T: TryFrom<&'b OscType<'a>, Error = ArgError>,
+    {
+        convert::arg(&self.args, index)
+    }
+
+    /// Convert all arguments to a tuple with one field per argument.
+    ///
+    /// Fails if an argument has the wrong type or if the number of arguments
+    /// differs from the number of fields.
+    ///
+    /// ```
+    /// use osc_types10::{Message, OscType};
+    ///
+    /// let msg = Message::new("/note", vec![OscType::Int(60), OscType::Float(0.8), OscType::String("piano")]);
+    /// let (note, velocity, patch) = msg.args_as::<(i32, f32, &str)>().unwrap();
+    /// assert_eq!((note, velocity, patch), (60, 0.8, "piano"));
+    /// ```
+    pub fn args_as<'b, T: FromArgs<'b, 'a>>(&'b self) -> Result<T, ArgError> {
+        T::from_args(&self.args)
+    }
 }
 
 /// OSC Bundle as defined in OSC 1.0 specification
 ///
 /// Bundles can contain both messages and nested bundles, allowing for hierarchical organization
 /// of OSC data with precise timing control.
+#[cfg(feature = "alloc")]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bundle<'a> {
     /// OSC time tag (64-bit NTP timestamp)
-    pub timetag: u64,
+    pub timetag: TimeTag,
     /// Packets contained in the bundle (messages and/or nested bundles)
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub packets: Vec<OscPacket<'a>>,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> Bundle<'a> {
     /// Create a new OSC bundle
-    pub fn new(timetag: u64, packets: Vec<OscPacket<'a>>) -> Self {
-        Self { timetag, packets }
+    ///
+    /// `timetag` accepts a [`TimeTag`] or its raw 64-bit representation.
+    pub fn new(timetag: impl Into<TimeTag>, packets: Vec<OscPacket<'a>>) -> Self {
+        Self {
+            timetag: timetag.into(),
+            packets,
+        }
     }
 
     /// Create a new OSC bundle with only messages (convenience method)
-    pub fn with_messages(timetag: u64, messages: Vec<Message<'a>>) -> Self {
+    pub fn with_messages(timetag: impl Into<TimeTag>, messages: Vec<Message<'a>>) -> Self {
         let packets = messages.into_iter().map(OscPacket::Message).collect();
         Self::new(timetag, packets)
     }
 
     /// Create a new empty bundle
-    pub fn empty(timetag: u64) -> Self {
+    pub fn empty(timetag: impl Into<TimeTag>) -> Self {
         Self::new(timetag, Vec::new())
     }
 
@@ -112,9 +402,9 @@ impl<'a> Bundle<'a> {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
-    use super::{Bundle, Message, OscPacket, OscType};
+    use super::{Bundle, Color, Message, MidiMessage, OscPacket, OscType, TimeTag};
 
     #[cfg(not(feature = "std"))]
     use alloc::vec;
@@ -143,6 +433,26 @@ mod tests {
     }
 
     #[test]
+    fn message_try_new_validates_address() {
+        assert!(Message::try_new("/synth/*", vec![]).is_ok());
+        assert!(Message::try_new("foo bar", vec![]).is_err());
+        assert!(Message::try_new("", vec![]).is_err());
+
+        let msg = Message::new("/synth/{1,2}", vec![]);
+        assert!(msg.address_pattern().unwrap().matches("/synth/2"));
+        assert!(Message::new("#bundle", vec![]).address_pattern().is_err());
+    }
+
+    #[test]
+    fn message_matches_method_addresses() {
+        let msg = Message::new("/synth/*/freq", vec![OscType::Float(440.0)]);
+
+        assert!(msg.matches("/synth/1/freq"));
+        assert!(!msg.matches("/synth/1/amp"));
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
     fn message_supports_mixed_types() {
         let msg = Message::new(
             "/mixed",
@@ -158,6 +468,54 @@ mod tests {
         assert_eq!(msg.args.len(), 4);
     }
 
+    #[test]
+    fn message_supports_optional_types() {
+        let msg = Message::new(
+            "/optional",
+            vec![
+                OscType::Long(-1),
+                OscType::Time(TimeTag::IMMEDIATELY),
+                OscType::Double(0.25),
+                OscType::Symbol("sym"),
+                OscType::Char('x'),
+                OscType::Color(Color::new(1, 2, 3, 4)),
+                OscType::Midi(MidiMessage::new(0, 0x90, 60, 127)),
+                OscType::Bool(true),
+                OscType::Bool(false),
+                OscType::Nil,
+                OscType::Inf,
+            ],
+        );
+
+        assert!(msg
+            .args
+            .iter()
+            .map(OscType::tag)
+            .eq(['h', 't', 'd', 'S', 'c', 'r', 'm', 'T', 'F', 'N', 'I']));
+    }
+
+    #[test]
+    fn message_supports_nested_arrays() {
+        let msg = Message::new(
+            "/array",
+            vec![
+                OscType::Int(1),
+                OscType::Array(vec![
+                    OscType::Float(0.5),
+                    OscType::Array(vec![OscType::String("deep")]),
+                ]),
+            ],
+        );
+
+        assert_eq!(msg.args[1].tag(), '[');
+        if let OscType::Array(ref items) = msg.args[1] {
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[1], OscType::Array(vec![OscType::String("deep")]));
+        } else {
+            panic!("Expected array argument");
+        }
+    }
+
     #[test]
     fn bundle_new_sets_timetag_and_packets() {
         let messages = vec![
@@ -0,0 +1,140 @@
+//! `osc!` and `osc_bundle!` literals.
+
+/// Items the macros and derives expand to, so callers need no
+/// `extern crate alloc`.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(not(feature = "std"))]
+    pub use alloc::{string::String, vec};
+    #[cfg(feature = "std")]
+    pub use std::{string::String, vec};
+
+    use core::fmt::{Display, Write as _};
+
+    use crate::{AddressError, ArgError, OscArg, OscType};
+
+    /// Convert the argument at `index`.
+    pub fn arg<T: OscArg>(args: &[OscType<'_>], index: usize) -> Result<T, ArgError> {
+        let arg = args.get(index).ok_or(ArgError::missing(index))?;
+        T::from_osc_arg(arg).map_err(|e| e.at(index))
+    }
+
+    /// Fail if there are more than `len` arguments.
+    pub fn no_extra(args: &[OscType<'_>], len: usize) -> Result<(), ArgError> {
+        crate::convert::no_extra(args, len)
+    }
+
+    /// Append a field to `address` as a single address component.
+    ///
+    /// Fails if it is empty, contains `/` or a character an address may not
+    /// hold; the error position is relative to the whole address.
+    pub fn push_component<T: Display + ?Sized>(
+        address: &mut String,
+        value: &T,
+    ) -> Result<(), AddressError> {
+        let start = address.len();
+        write!(address, "{value}").expect("a Display impl returned an error");
+        crate::address::check_component(address, start)
+    }
+}
+
+/// Build a [`Message`](crate::Message) from an address and arguments.
+///
+/// Each argument is converted with [`OscType::from`](crate::OscType), so its
+/// Rust type picks the variant: `1` is an `Int`, `2.0f32` a `Float`, `2.0` a
+/// `Double`, `"x"` a `String`, `true` a `Bool` and a `Vec<OscType>` an
+/// `Array`. `OscType` values are passed through unchanged.
+///
+/// ```
+/// use osc_types10::{osc, Message, OscType};
+///
+/// let msg = osc!("/a", 1, 2.0f32, "x");
+/// assert_eq!(
+///     msg,
+///     Message::new("/a", vec![OscType::Int(1), OscType::Float(2.0), OscType::String("x")])
+/// );
+/// assert!(osc!("/ping").args.is_empty());
+/// ```
+#[macro_export]
+macro_rules! osc {
+    ($address:expr $(, $arg:expr)* $(,)?) => {
+        $crate::Message::new(
+            $address,
+            $crate::__private::vec![$($crate::OscType::from($arg)),*],
+        )
+    };
+}
+
+/// Build a [`Bundle`](crate::Bundle) from a time tag and its elements.
+///
+/// The time tag is anything convertible into a [`TimeTag`](crate::TimeTag);
+/// the elements, separated from it by `;`, are messages or bundles, typically
+/// written with [`osc!`] and `osc_bundle!` themselves.
+///
+/// ```
+/// use osc_types10::{osc, osc_bundle, OscPacket};
+///
+/// let bundle = osc_bundle!(1; osc!("/a", 1), osc_bundle!(2; osc!("/b", "x")));
+/// assert_eq!(bundle.packets.len(), 2);
+/// assert!(matches!(&bundle.packets[1], OscPacket::Bundle(inner) if inner.timetag == 2));
+/// assert!(osc_bundle!(3).packets.is_empty());
+/// ```
+#[macro_export]
+macro_rules! osc_bundle {
+    ($timetag:expr $(; $($element:expr),* $(,)?)?) => {
+        $crate::Bundle::new(
+            $timetag,
+            $crate::__private::vec![$($($crate::OscPacket::from($element)),*)?],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bundle, Color, Message, OscPacket, OscType};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn infers_variants_from_literals() {
+        let blob: &[u8] = &[1, 2];
+        let msg = osc!(
+            "/all",
+            1,
+            2i64,
+            3.0f32,
+            4.0,
+            "s",
+            blob,
+            'c',
+            true,
+            Color::new(1, 2, 3, 4),
+            OscType::Nil,
+            vec![OscType::Int(5)],
+        );
+        assert_eq!(msg.address, "/all");
+        let tags: Vec<char> = msg.args.iter().map(OscType::tag).collect();
+        assert_eq!(
+            tags,
+            ['i', 'h', 'f', 'd', 's', 'b', 'c', 'T', 'r', 'N', '[']
+        );
+    }
+
+    #[test]
+    fn nests_bundles() {
+        let bundle = osc_bundle!(10; osc!("/a"), osc_bundle!(20; osc!("/b", 1)),);
+        let expected = Bundle::new(
+            10,
+            vec![
+                OscPacket::Message(Message::new("/a", vec![])),
+                OscPacket::Bundle(Bundle::with_messages(
+                    20,
+                    vec![Message::new("/b", vec![OscType::Int(1)])],
+                )),
+            ],
+        );
+        assert_eq!(bundle, expected);
+        assert_eq!(osc_bundle!(0), Bundle::empty(0));
+    }
+}
@@ -0,0 +1,102 @@
+//! Mapping Rust types to and from whole messages.
+//!
+//! The traits can be implemented by hand; with the `derive` feature they can
+//! also be derived with the macros of the `osc-derive` crate.
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    AddressError, ArgError, Color, Message, MessageBuf, MidiMessage, OscType, OscTypeBuf, TimeTag,
+};
+
+/// A Rust type that is carried by a single OSC argument.
+///
+/// Implemented for the argument value types, `String` (from strings and
+/// symbols), `Vec<u8>` (blobs) and `Vec<T>` (arrays). Fieldless enums can
+/// derive it to be sent as an int or a string.
+pub trait OscArg: Sized {
+    /// Convert to an owned argument.
+    fn to_osc_arg(&self) -> OscTypeBuf;
+
+    /// Convert from an argument.
+    fn from_osc_arg(arg: &OscType<'_>) -> Result<Self, ArgError>;
+}
+
+macro_rules! copy_args {
+    ($($ty:ty => $variant:ident;)*) => {$(
+        impl OscArg for $ty {
+            fn to_osc_arg(&self) -> OscTypeBuf {
+                OscTypeBuf::$variant(*self)
+            }
+
+            fn from_osc_arg(arg: &OscType<'_>) -> Result<Self, ArgError> {
+                Self::try_from(arg)
+            }
+        }
+    )*};
+}
+
+copy_args! {
+    i32 => Int;
+    f32 => Float;
+    i64 => Long;
+    TimeTag => Time;
+    f64 => Double;
+    char => Char;
+    Color => Color;
+    MidiMessage => Midi;
+    bool => Bool;
+}
+
+impl OscArg for String {
+    fn to_osc_arg(&self) -> OscTypeBuf {
+        OscTypeBuf::String(self.clone())
+    }
+
+    fn from_osc_arg(arg: &OscType<'_>) -> Result<Self, ArgError> {
+        <&str>::try_from(arg).map(String::from)
+    }
+}
+
+impl OscArg for Vec<u8> {
+    fn to_osc_arg(&self) -> OscTypeBuf {
+        OscTypeBuf::Blob(self.clone())
+    }
+
+    fn from_osc_arg(arg: &OscType<'_>) -> Result<Self, ArgError> {
+        <&[u8]>::try_from(arg).map(<[u8]>::to_vec)
+    }
+}
+
+/// Arrays. `u8` is not an [`OscArg`], so `Vec<u8>` stays a blob.
+impl<T: OscArg> OscArg for Vec<T> {
+    fn to_osc_arg(&self) -> OscTypeBuf {
+        OscTypeBuf::Array(self.iter().map(T::to_osc_arg).collect())
+    }
+
+    fn from_osc_arg(arg: &OscType<'_>) -> Result<Self, ArgError> {
+        <&[OscType<'_>]>::try_from(arg)?
+            .iter()
+            .map(T::from_osc_arg)
+            .collect()
+    }
+}
+
+/// A Rust type that converts to a message.
+pub trait ToOscMessage {
+    /// Build the message.
+    ///
+    /// Fails if the address built from the value is not a valid OSC address,
+    /// e.g. because a field formatted into it is empty or contains a space.
+    fn to_osc_message(&self) -> Result<MessageBuf, AddressError>;
+}
+
+/// A Rust type that can be read from a message.
+pub trait FromOscMessage: Sized {
+    /// Read the address and arguments of `msg`.
+    fn from_osc_message(msg: &Message<'_>) -> Result<Self, ArgError>;
+}
@@ -0,0 +1,174 @@
+//! Matching one address pattern against many registered addresses at once.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::address::AddressError;
+use crate::space::AddressSpace;
+
+/// A set of method addresses compiled into a trie for pattern dispatch.
+///
+/// Each registered address gets an id (its insertion index). Matching walks the
+/// trie one pattern part at a time: literal parts are resolved with a single
+/// lookup, `{a,b}` groups with one lookup per alternative, and any other part
+/// with wildcards is matched against every child of the current node. Branches
+/// that cannot match are never visited, so a pattern of literal parts costs one
+/// lookup per part, while each wildcard part costs a scan of the children
+/// under the nodes reached so far.
+///
+/// ```
+/// use osc_types10::AddressMatcher;
+///
+/// let mut matcher = AddressMatcher::new();
+/// let freq = matcher.insert("/synth/1/freq").unwrap();
+/// let amp = matcher.insert("/synth/1/amp").unwrap();
+/// matcher.insert("/mixer/1/gain").unwrap();
+///
+/// assert_eq!(matcher.matches("/synth/1/*"), vec![freq, amp]);
+/// assert_eq!(matcher.matches("/synth/?/freq"), vec![freq]);
+/// assert!(matcher.matches("/synth/2/*").is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AddressMatcher {
+    ids: AddressSpace<usize>,
+}
+
+impl AddressMatcher {
+    /// Create an empty matcher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a method address and return its id.
+    ///
+    /// Registering the same address twice returns the id it was first given.
+    /// Fails if `address` is not a valid OSC method address.
+    pub fn insert(&mut self, address: &str) -> Result<usize, AddressError> {
+        if let Some(&id) = self.ids.get(address) {
+            return Ok(id);
+        }
+        let id = self.ids.len();
+        self.ids.insert(address, id)?;
+        Ok(id)
+    }
+
+    /// Returns the id of `address` if it is registered.
+    pub fn get(&self, address: &str) -> Option<usize> {
+        self.ids.get(address).copied()
+    }
+
+    /// Number of registered addresses.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if no address is registered.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Ids of every registered address matched by `pattern`, in ascending order.
+    ///
+    /// A malformed pattern matches nothing.
+    pub fn matches(&self, pattern: &str) -> Vec<usize> {
+        let mut ids = Vec::new();
+        self.for_each_match(pattern, |id| ids.push(id));
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Call `f` with the id of every registered address matched by `pattern`.
+    ///
+    /// Ids are reported in trie order without allocating. A malformed pattern
+    /// matches nothing.
+    pub fn for_each_match(&self, pattern: &str, mut f: impl FnMut(usize)) {
+        self.ids.for_each_match(pattern, |&id| f(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AddressMatcher;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+
+    fn matcher() -> AddressMatcher {
+        let mut matcher = AddressMatcher::new();
+        for address in [
+            "/synth/1/freq",
+            "/synth/1/amp",
+            "/synth/2/freq",
+            "/synth/2/amp",
+            "/mixer/1/gain",
+            "/mixer",
+        ] {
+            matcher.insert(address).unwrap();
+        }
+        matcher
+    }
+
+    #[test]
+    fn insert_assigns_ids_in_order_and_deduplicates() {
+        let mut matcher = matcher();
+
+        assert_eq!(matcher.len(), 6);
+        assert_eq!(matcher.insert("/synth/2/freq"), Ok(2));
+        assert_eq!(matcher.insert("/new"), Ok(6));
+        assert_eq!(matcher.get("/mixer/1/gain"), Some(4));
+        assert_eq!(matcher.get("/mixer/1"), None);
+        assert!(!matcher.is_empty());
+    }
+
+    #[test]
+    fn literal_pattern_matches_single_address() {
+        let matcher = matcher();
+
+        assert_eq!(matcher.matches("/synth/2/amp"), vec![3]);
+        assert_eq!(matcher.matches("/mixer"), vec![5]);
+        assert!(matcher.matches("/synth/3/amp").is_empty());
+    }
+
+    #[test]
+    fn wildcard_patterns_return_every_hit() {
+        let matcher = matcher();
+
+        assert_eq!(matcher.matches("/synth/*/freq"), vec![0, 2]);
+        assert_eq!(matcher.matches("/synth/[12]/*"), vec![0, 1, 2, 3]);
+        assert_eq!(matcher.matches("/{synth,mixer}/1/{amp,gain}"), vec![1, 4]);
+        assert_eq!(matcher.matches("/synth/{1,1}/amp"), vec![1]);
+        assert_eq!(matcher.matches("/*"), vec![5]);
+        assert!(matcher.matches("/*/*/*/*").is_empty());
+    }
+
+    #[test]
+    fn agrees_with_per_route_matching() {
+        let matcher = matcher();
+        let addresses = [
+            "/synth/1/freq",
+            "/synth/1/amp",
+            "/synth/2/freq",
+            "/synth/2/amp",
+            "/mixer/1/gain",
+            "/mixer",
+        ];
+
+        for pattern in ["/s?nth/[!1]/*", "/*/1/{freq,gain}", "/mixer*", "/*/*/a*"] {
+            let expected: Vec<usize> = (0..addresses.len())
+                .filter(|&i| crate::matches_address(pattern, addresses[i]))
+                .collect();
+            assert_eq!(matcher.matches(pattern), expected, "{pattern}");
+        }
+    }
+
+    #[test]
+    fn malformed_pattern_matches_nothing() {
+        let matcher = matcher();
+
+        assert!(matcher.matches("/synth/[1/freq").is_empty());
+        assert!(matcher.matches("synth/1/freq").is_empty());
+    }
+}
@@ -0,0 +1,432 @@
+//! Owned counterparts of the borrowed packet types.
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{Bundle, Color, Message, MidiMessage, OscPacket, OscType, TimeTag};
+
+/// Owned counterpart of [`OscType`]
+///
+/// Strings and blobs are stored in their own allocations, so values can outlive
+/// the buffer they were decoded from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscTypeBuf {
+    /// 32-bit integer (i)
+    Int(i32),
+    /// 32-bit IEEE 754 float (f)
+    Float(f32),
+    /// Null-terminated string (s)
+    String(String),
+    /// Binary blob (b)
+    Blob(Vec<u8>),
+    /// 64-bit integer (h)
+    Long(i64),
+    /// OSC time tag (t)
+    Time(TimeTag),
+    /// 64-bit IEEE 754 double (d)
+    Double(f64),
+    /// Alternate string type, e.g. for symbols (S)
+    Symbol(String),
+    /// ASCII character sent as 32 bits (c)
+    Char(char),
+    /// 32-bit RGBA color (r)
+    Color(Color),
+    /// 4-byte MIDI message (m)
+    Midi(MidiMessage),
+    /// Boolean carried by the type tag alone (T or F)
+    Bool(bool),
+    /// Nil, carries no data (N)
+    Nil,
+    /// Infinitum, carries no data (I)
+    Inf,
+    /// Array of arguments, delimited by `[` and `]` in the type tag string
+    Array(Vec<OscTypeBuf>),
+}
+
+impl OscTypeBuf {
+    /// Returns the type tag character used for this argument in a type tag string.
+    pub fn tag(&self) -> char {
+        match self {
+            OscTypeBuf::Int(_) => 'i',
+            OscTypeBuf::Float(_) => 'f',
+            OscTypeBuf::String(_) => 's',
+            OscTypeBuf::Blob(_) => 'b',
+            OscTypeBuf::Long(_) => 'h',
+            OscTypeBuf::Time(_) => 't',
+            OscTypeBuf::Double(_) => 'd',
+            OscTypeBuf::Symbol(_) => 'S',
+            OscTypeBuf::Char(_) => 'c',
+            OscTypeBuf::Color(_) => 'r',
+            OscTypeBuf::Midi(_) => 'm',
+            OscTypeBuf::Bool(true) => 'T',
+            OscTypeBuf::Bool(false) => 'F',
+            OscTypeBuf::Nil => 'N',
+            OscTypeBuf::Inf => 'I',
+            OscTypeBuf::Array(_) => '[',
+        }
+    }
+
+    /// Borrow as an [`OscType`].
+    ///
+    /// Only arrays allocate, to collect their borrowed items.
+    pub fn as_osc_type(&self) -> OscType<'_> {
+        match self {
+            OscTypeBuf::Int(v) => OscType::Int(*v),
+            OscTypeBuf::Float(v) => OscType::Float(*v),
+            OscTypeBuf::String(s) => OscType::String(s),
+            OscTypeBuf::Blob(b) => OscType::Blob(b),
+            OscTypeBuf::Long(v) => OscType::Long(*v),
+            OscTypeBuf::Time(t) => OscType::Time(*t),
+            OscTypeBuf::Double(v) => OscType::Double(*v),
+            OscTypeBuf::Symbol(s) => OscType::Symbol(s),
+            OscTypeBuf::Char(c) => OscType::Char(*c),
+            OscTypeBuf::Color(c) => OscType::Color(*c),
+            OscTypeBuf::Midi(m) => OscType::Midi(*m),
+            OscTypeBuf::Bool(b) => OscType::Bool(*b),
+            OscTypeBuf::Nil => OscType::Nil,
+            OscTypeBuf::Inf => OscType::Inf,
+            OscTypeBuf::Array(items) => {
+                OscType::Array(items.iter().map(OscTypeBuf::as_osc_type).collect())
+            }
+        }
+    }
+}
+
+/// An argument read in place: a borrowed [`OscType`] or an owned [`OscTypeBuf`].
+///
+/// Lets code that walks the arguments of a message, such as the encoders in
+/// `osc-codec10` and `Display`, accept borrowed and owned messages alike
+/// without converting one into the other.
+pub trait AsOscType: Sized {
+    /// The type tag of this argument (`'['` for arrays).
+    fn tag(&self) -> char;
+
+    /// The items of an array, or `None` for any other argument.
+    fn items(&self) -> Option<&[Self]>;
+
+    /// Borrow as an [`OscType`].
+    ///
+    /// Only arrays allocate; walk them with [`items`](Self::items) instead.
+    fn as_osc_type(&self) -> OscType<'_>;
+}
+
+impl AsOscType for OscType<'_> {
+    fn tag(&self) -> char {
+        OscType::tag(self)
+    }
+
+    fn items(&self) -> Option<&[Self]> {
+        match self {
+            OscType::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn as_osc_type(&self) -> OscType<'_> {
+        self.clone()
+    }
+}
+
+impl AsOscType for OscTypeBuf {
+    fn tag(&self) -> char {
+        OscTypeBuf::tag(self)
+    }
+
+    fn items(&self) -> Option<&[Self]> {
+        match self {
+            OscTypeBuf::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn as_osc_type(&self) -> OscType<'_> {
+        OscTypeBuf::as_osc_type(self)
+    }
+}
+
+impl OscType<'_> {
+    /// Copy borrowed strings and blobs into an [`OscTypeBuf`].
+    pub fn into_owned(self) -> OscTypeBuf {
+        OscTypeBuf::from(&self)
+    }
+}
+
+impl From<OscType<'_>> for OscTypeBuf {
+    fn from(arg: OscType<'_>) -> Self {
+        OscTypeBuf::from(&arg)
+    }
+}
+
+impl From<&OscType<'_>> for OscTypeBuf {
+    fn from(arg: &OscType<'_>) -> Self {
+        match *arg {
+            OscType::Int(v) => OscTypeBuf::Int(v),
+            OscType::Float(v) => OscTypeBuf::Float(v),
+            OscType::String(s) => OscTypeBuf::String(String::from(s)),
+            OscType::Blob(b) => OscTypeBuf::Blob(b.to_vec()),
+            OscType::Long(v) => OscTypeBuf::Long(v),
+            OscType::Time(t) => OscTypeBuf::Time(t),
+            OscType::Double(v) => OscTypeBuf::Double(v),
+            OscType::Symbol(s) => OscTypeBuf::Symbol(String::from(s)),
+            OscType::Char(c) => OscTypeBuf::Char(c),
+            OscType::Color(c) => OscTypeBuf::Color(c),
+            OscType::Midi(m) => OscTypeBuf::Midi(m),
+            OscType::Bool(b) => OscTypeBuf::Bool(b),
+            OscType::Nil => OscTypeBuf::Nil,
+            OscType::Inf => OscTypeBuf::Inf,
+            OscType::Array(ref items) => {
+                OscTypeBuf::Array(items.iter().map(OscTypeBuf::from).collect())
+            }
+        }
+    }
+}
+
+/// Owned counterpart of [`Message`]
+///
+/// Can be stored, queued or sent across threads independently of any receive
+/// buffer. The encoders in `osc-codec10` and `Display` accept it directly.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageBuf {
+    /// OSC address pattern
+    pub address: String,
+    /// Arguments of the message
+    pub args: Vec<OscTypeBuf>,
+}
+
+impl MessageBuf {
+    /// Create a new owned OSC message
+    pub fn new(address: impl Into<String>, args: Vec<OscTypeBuf>) -> Self {
+        Self {
+            address: address.into(),
+            args,
+        }
+    }
+}
+
+impl Message<'_> {
+    /// Copy the address and arguments into a [`MessageBuf`]
+    pub fn into_owned(self) -> MessageBuf {
+        MessageBuf {
+            address: String::from(self.address),
+            args: self.args.into_iter().map(OscType::into_owned).collect(),
+        }
+    }
+}
+
+impl From<Message<'_>> for MessageBuf {
+    fn from(msg: Message<'_>) -> Self {
+        msg.into_owned()
+    }
+}
+
+impl From<&Message<'_>> for MessageBuf {
+    fn from(msg: &Message<'_>) -> Self {
+        MessageBuf {
+            address: String::from(msg.address),
+            args: msg.args.iter().map(OscTypeBuf::from).collect(),
+        }
+    }
+}
+
+/// Strings and blobs are borrowed, but the argument list and every array are
+/// collected into new `Vec`s.
+impl<'a> From<&'a MessageBuf> for Message<'a> {
+    fn from(msg: &'a MessageBuf) -> Self {
+        Message::new(
+            &msg.address,
+            msg.args.iter().map(OscTypeBuf::as_osc_type).collect(),
+        )
+    }
+}
+
+/// Owned counterpart of [`Bundle`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BundleBuf {
+    /// OSC time tag (64-bit NTP timestamp)
+    pub timetag: TimeTag,
+    /// Packets contained in the bundle (messages and/or nested bundles)
+    pub packets: Vec<OscPacketBuf>,
+}
+
+impl BundleBuf {
+    /// Create a new owned OSC bundle
+    pub fn new(timetag: impl Into<TimeTag>, packets: Vec<OscPacketBuf>) -> Self {
+        Self {
+            timetag: timetag.into(),
+            packets,
+        }
+    }
+}
+
+impl Bundle<'_> {
+    /// Copy every contained packet into a [`BundleBuf`]
+    pub fn into_owned(self) -> BundleBuf {
+        BundleBuf {
+            timetag: self.timetag,
+            packets: self
+                .packets
+                .into_iter()
+                .map(OscPacket::into_owned)
+                .collect(),
+        }
+    }
+}
+
+impl From<Bundle<'_>> for BundleBuf {
+    fn from(bundle: Bundle<'_>) -> Self {
+        bundle.into_owned()
+    }
+}
+
+impl From<&Bundle<'_>> for BundleBuf {
+    fn from(bundle: &Bundle<'_>) -> Self {
+        BundleBuf {
+            timetag: bundle.timetag,
+            packets: bundle.packets.iter().map(OscPacketBuf::from).collect(),
+        }
+    }
+}
+
+/// Collects new `Vec`s for every nested packet, argument list and array.
+impl<'a> From<&'a BundleBuf> for Bundle<'a> {
+    fn from(bundle: &'a BundleBuf) -> Self {
+        Bundle::new(
+            bundle.timetag,
+            bundle.packets.iter().map(OscPacket::from).collect(),
+        )
+    }
+}
+
+/// Owned counterpart of [`OscPacket`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum OscPacketBuf {
+    /// An OSC message
+    Message(MessageBuf),
+    /// An OSC bundle
+    Bundle(BundleBuf),
+}
+
+impl OscPacket<'_> {
+    /// Copy the packet into an [`OscPacketBuf`]
+    pub fn into_owned(self) -> OscPacketBuf {
+        match self {
+            OscPacket::Message(msg) => OscPacketBuf::Message(msg.into_owned()),
+            OscPacket::Bundle(bundle) => OscPacketBuf::Bundle(bundle.into_owned()),
+        }
+    }
+}
+
+impl From<OscPacket<'_>> for OscPacketBuf {
+    fn from(packet: OscPacket<'_>) -> Self {
+        packet.into_owned()
+    }
+}
+
+impl From<&OscPacket<'_>> for OscPacketBuf {
+    fn from(packet: &OscPacket<'_>) -> Self {
+        match packet {
+            OscPacket::Message(msg) => OscPacketBuf::Message(msg.into()),
+            OscPacket::Bundle(bundle) => OscPacketBuf::Bundle(bundle.into()),
+        }
+    }
+}
+
+/// Allocates like the [`Message`] and [`Bundle`] conversions.
+impl<'a> From<&'a OscPacketBuf> for OscPacket<'a> {
+    fn from(packet: &'a OscPacketBuf) -> Self {
+        match packet {
+            OscPacketBuf::Message(msg) => OscPacket::Message(msg.into()),
+            OscPacketBuf::Bundle(bundle) => OscPacket::Bundle(bundle.into()),
+        }
+    }
+}
+
+impl From<MessageBuf> for OscPacketBuf {
+    fn from(msg: MessageBuf) -> Self {
+        OscPacketBuf::Message(msg)
+    }
+}
+
+impl From<BundleBuf> for OscPacketBuf {
+    fn from(bundle: BundleBuf) -> Self {
+        OscPacketBuf::Bundle(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BundleBuf, MessageBuf, OscPacketBuf, OscTypeBuf};
+    use crate::{Bundle, Message, OscPacket, OscType, TimeTag};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::String, vec};
+
+    fn message(data: &[u8]) -> Message<'_> {
+        Message::new(
+            "/owned",
+            vec![
+                OscType::String("text"),
+                OscType::Blob(data),
+                OscType::Array(vec![OscType::Symbol("sym"), OscType::Int(1)]),
+            ],
+        )
+    }
+
+    #[test]
+    fn message_outlives_its_buffer() {
+        let owned = {
+            let data = vec![1u8, 2, 3];
+            message(&data).into_owned()
+        };
+
+        assert_eq!(owned.address, "/owned");
+        assert_eq!(owned.args[1], OscTypeBuf::Blob(vec![1, 2, 3]));
+        assert_eq!(Message::from(&owned), message(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn from_reference_copies() {
+        let data = [9u8];
+        let msg = message(&data);
+        let owned = MessageBuf::from(&msg);
+
+        assert_eq!(owned, msg.clone().into_owned());
+        assert_eq!(Message::from(&owned), msg);
+        assert_eq!(owned.args[2].tag(), '[');
+    }
+
+    #[test]
+    fn bundle_and_packet_roundtrip() {
+        let data = [4u8, 5];
+        let mut bundle = Bundle::with_messages(TimeTag::new(5, 6), vec![message(&data)]);
+        bundle.add_bundle(Bundle::empty(TimeTag::IMMEDIATELY));
+        let packet = OscPacket::Bundle(bundle.clone());
+
+        let owned = OscPacketBuf::from(&packet);
+        assert_eq!(OscPacket::from(&owned), packet);
+        assert_eq!(packet.into_owned(), owned);
+
+        let OscPacketBuf::Bundle(owned_bundle) = owned else {
+            panic!("Expected bundle");
+        };
+        assert_eq!(owned_bundle.timetag, TimeTag::new(5, 6));
+        assert_eq!(Bundle::from(&owned_bundle), bundle);
+        assert_eq!(BundleBuf::from(bundle), owned_bundle);
+    }
+
+    #[test]
+    fn owned_types_are_send_and_static() {
+        fn assert_send_static<T: Send + 'static>(_: &T) {}
+
+        let owned = MessageBuf::new(String::from("/a"), vec![OscTypeBuf::Nil]);
+        assert_send_static(&owned);
+        assert_send_static(&OscPacketBuf::from(owned));
+    }
+}
@@ -0,0 +1,577 @@
+//! OSC 1.0 address pattern matching.
+
+use core::fmt;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec;
+
+/// Reason an address pattern is malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternErrorKind {
+    /// The pattern does not start with `/`.
+    MissingLeadingSlash,
+    /// A character that may not appear in an OSC address (space, `#`, `,` outside
+    /// `{}`, control characters or non-ASCII).
+    InvalidCharacter(char),
+    /// A `[` without a closing `]`.
+    UnclosedBracket,
+    /// A `{` without a closing `}`.
+    UnclosedBrace,
+    /// A `]` or `}` without an opening counterpart.
+    UnmatchedClose(char),
+    /// A `[]` or `[!]` list that matches nothing.
+    EmptyBracket,
+    /// A range such as `[z-a]` whose start is greater than its end.
+    InvalidRange,
+    /// A `[`, `{` or wildcard inside a `[...]` or `{...}` group, or a `/` inside one.
+    NestedGroup(char),
+}
+
+/// Error returned for a malformed OSC address pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatternError {
+    kind: PatternErrorKind,
+    position: usize,
+}
+
+impl PatternError {
+    /// What is wrong with the pattern.
+    pub fn kind(&self) -> PatternErrorKind {
+        self.kind
+    }
+
+    /// Byte offset in the pattern where the problem was detected.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for PatternErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternErrorKind::MissingLeadingSlash => f.write_str("pattern must start with '/'"),
+            PatternErrorKind::InvalidCharacter(c) => write!(f, "invalid character {c:?}"),
+            PatternErrorKind::UnclosedBracket => f.write_str("unclosed '['"),
+            PatternErrorKind::UnclosedBrace => f.write_str("unclosed '{'"),
+            PatternErrorKind::UnmatchedClose(c) => write!(f, "unmatched '{c}'"),
+            PatternErrorKind::EmptyBracket => f.write_str("empty character list"),
+            PatternErrorKind::InvalidRange => f.write_str("character range start is after its end"),
+            PatternErrorKind::NestedGroup(c) => write!(f, "'{c}' is not allowed inside a group"),
+        }
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatternError {}
+
+/// A validated OSC 1.0 address pattern.
+///
+/// Supports the full OSC 1.0 grammar, matched part-by-part on `/`-separated
+/// components:
+/// - `?` matches any single character
+/// - `*` matches any sequence of zero or more characters
+/// - `[abc]`, `[a-z]` match one character from the list; `[!abc]` negates it
+/// - `{foo,bar}` matches any of the comma-separated strings
+///
+/// None of the wildcards match across a `/`.
+///
+/// ```
+/// use osc_types10::Pattern;
+///
+/// let pattern = Pattern::new("/synth/[0-9]/{freq,amp}").unwrap();
+/// assert!(pattern.matches("/synth/3/freq"));
+/// assert!(!pattern.matches("/synth/3/pan"));
+/// assert!(!pattern.matches("/synth/10/amp"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pattern<'a> {
+    pattern: &'a str,
+}
+
+impl<'a> Pattern<'a> {
+    /// Validate `pattern` and wrap it.
+    pub fn new(pattern: &'a str) -> Result<Self, PatternError> {
+        validate_pattern(pattern)?;
+        Ok(Self { pattern })
+    }
+
+    /// The pattern string.
+    pub fn as_str(&self) -> &'a str {
+        self.pattern
+    }
+
+    /// Returns `true` if `address` is matched by this pattern.
+    pub fn matches(&self, address: &str) -> bool {
+        matches_parts(self.pattern, address)
+    }
+
+    /// Returns `true` if the pattern contains no wildcard or group.
+    pub fn is_literal(&self) -> bool {
+        !self.pattern.bytes().any(is_pattern_byte)
+    }
+}
+
+/// Check that `pattern` is a well-formed OSC 1.0 address pattern.
+pub fn validate_pattern(pattern: &str) -> Result<(), PatternError> {
+    let err = |kind, position| Err(PatternError { kind, position });
+    if !pattern.starts_with('/') {
+        return err(PatternErrorKind::MissingLeadingSlash, 0);
+    }
+
+    // Position of the currently open `[` or `{`, if any.
+    let mut open: Option<(char, usize)> = None;
+    // Characters seen in the current `[...]` list, for empty and range checks.
+    let mut list_len = 0;
+    let mut prev: Option<char> = None;
+    let mut range_pending = false;
+    // Whether the previous list item was a range; a `-` after one is literal.
+    let mut after_range = false;
+    for (pos, c) in pattern.char_indices() {
+        if !c.is_ascii() || c.is_ascii_control() || c == ' ' || c == '#' {
+            return err(PatternErrorKind::InvalidCharacter(c), pos);
+        }
+        match open {
+            Some(('[', start)) => match c {
+                ']' => {
+                    if list_len == 0 {
+                        return err(PatternErrorKind::EmptyBracket, start);
+                    }
+                    open = None;
+                }
+                '!' if list_len == 0 && prev == Some('[') => {}
+                '[' | '{' | '}' | '*' | '?' | '/' => {
+                    return err(PatternErrorKind::NestedGroup(c), pos);
+                }
+                '-' if list_len > 0 && !range_pending && !after_range => range_pending = true,
+                _ => {
+                    after_range = range_pending;
+                    if range_pending {
+                        // `prev` is the `-`; the range start is the character before it.
+                        let start_char = pattern[..pos - 1].chars().next_back();
+                        if start_char.is_some_and(|s| s > c) {
+                            return err(PatternErrorKind::InvalidRange, pos);
+                        }
+                        range_pending = false;
+                    }
+                    list_len += 1;
+                }
+            },
+            Some((_, _)) => match c {
+                '}' => open = None,
+                '[' | '{' | ']' | '*' | '?' | '/' => {
+                    return err(PatternErrorKind::NestedGroup(c), pos);
+                }
+                _ => {}
+            },
+            None => match c {
+                '[' => {
+                    open = Some(('[', pos));
+                    list_len = 0;
+                    range_pending = false;
+                    after_range = false;
+                }
+                '{' => open = Some(('{', pos)),
+                ']' | '}' => return err(PatternErrorKind::UnmatchedClose(c), pos),
+                ',' => return err(PatternErrorKind::InvalidCharacter(c), pos),
+                _ => {}
+            },
+        }
+        prev = Some(c);
+    }
+
+    match open {
+        Some(('[', start)) => err(PatternErrorKind::UnclosedBracket, start),
+        Some((_, start)) => err(PatternErrorKind::UnclosedBrace, start),
+        None => Ok(()),
+    }
+}
+
+/// Returns `true` if the OSC address `pattern` matches `address`.
+///
+/// Both are split on `/` and must have the same number of parts. Malformed
+/// patterns never match; use [`validate_pattern`] or [`Pattern::new`] to find
+/// out why.
+pub fn matches_address(pattern: &str, address: &str) -> bool {
+    validate_pattern(pattern).is_ok() && matches_parts(pattern, address)
+}
+
+/// Match a validated pattern against `address` component by component.
+fn matches_parts(pattern: &str, address: &str) -> bool {
+    let mut pattern_parts = pattern.split('/');
+    let mut address_parts = address.split('/');
+    loop {
+        match (pattern_parts.next(), address_parts.next()) {
+            (Some(p), Some(a)) => {
+                if !matches_part(p, a) {
+                    return false;
+                }
+            }
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Match a single `/`-free pattern component against an address component.
+///
+/// The pattern part is assumed to come from a validated pattern. Matching
+/// takes time proportional to the product of both lengths; nothing is retried
+/// per `*` or per `{}` alternative.
+pub(crate) fn matches_part(pattern: &str, name: &str) -> bool {
+    if !pattern.contains('{') {
+        return matches_part_without_groups(pattern, name);
+    }
+    // One bit per byte offset into the pattern, plus one for its end.
+    let words = pattern.len() / 64 + 1;
+    if words <= INLINE_STATE_WORDS {
+        let mut states = [0; 2 * INLINE_STATE_WORDS];
+        let (current, next) = states.split_at_mut(INLINE_STATE_WORDS);
+        return matches_part_with_groups(pattern, name, &mut current[..words], &mut next[..words]);
+    }
+    #[cfg(feature = "alloc")]
+    {
+        matches_part_with_groups(pattern, name, &mut vec![0; words], &mut vec![0; words])
+    }
+    #[cfg(not(feature = "alloc"))]
+    {
+        // Without an allocator there is no room for the states of such a long
+        // component; fall back to trying the alternatives one by one.
+        matches_part_by_alternatives(pattern, name)
+    }
+}
+
+/// State words kept on the stack by [`matches_part`], enough for components
+/// of up to 511 bytes.
+const INLINE_STATE_WORDS: usize = 8;
+
+/// Match a component without `{}` groups.
+///
+/// Every item but `*` matches exactly one character, so on a mismatch it is
+/// enough to let the most recent `*` absorb one more character.
+fn matches_part_without_groups(pattern: &str, name: &str) -> bool {
+    let p = pattern.as_bytes();
+    let (mut pi, mut ni) = (0, 0);
+    // Pattern offset after the most recent `*` and the name offset it resumes from.
+    let mut star = None;
+    while let Some(c) = name[ni..].chars().next() {
+        if let Some(next) = step(pattern, pi, c) {
+            pi = next;
+            ni += c.len_utf8();
+            continue;
+        }
+        if p.get(pi) == Some(&b'*') {
+            pi += 1;
+            star = Some((pi, ni));
+            continue;
+        }
+        let Some((after_star, resume)) = star else {
+            return false;
+        };
+        let skipped = name[resume..].chars().next().map_or(1, char::len_utf8);
+        star = Some((after_star, resume + skipped));
+        pi = after_star;
+        ni = resume + skipped;
+    }
+    p[pi..].iter().all(|&b| b == b'*')
+}
+
+/// Match the single-character item at `pattern[pi..]` against `c`, returning
+/// the offset after the item. `*`, groups and the end of the pattern never match.
+fn step(pattern: &str, pi: usize, c: char) -> Option<usize> {
+    match *pattern.as_bytes().get(pi)? {
+        b'*' | b'{' | b',' | b'}' => None,
+        b'?' => Some(pi + 1),
+        b'[' => {
+            let close = pi + pattern[pi..].find(']')?;
+            list_contains(&pattern[pi + 1..close], c).then_some(close + 1)
+        }
+        b => (b as char == c).then_some(pi + 1),
+    }
+}
+
+/// Match a component with `{}` groups by simulating it as an automaton whose
+/// states are byte offsets into `pattern`. `current` and `next` must have room
+/// for `pattern.len() + 1` bits.
+fn matches_part_with_groups(
+    pattern: &str,
+    name: &str,
+    current: &mut [u64],
+    next: &mut [u64],
+) -> bool {
+    let (mut current, mut next) = (current, next);
+    current.fill(0);
+    add_state(pattern, current, 0);
+    for c in name.chars() {
+        next.fill(0);
+        for pi in 0..pattern.len() {
+            if !has_state(current, pi) {
+                continue;
+            }
+            if pattern.as_bytes()[pi] == b'*' {
+                add_state(pattern, next, pi);
+            } else if let Some(after) = step(pattern, pi, c) {
+                add_state(pattern, next, after);
+            }
+        }
+        if next.iter().all(|&word| word == 0) {
+            return false;
+        }
+        core::mem::swap(&mut current, &mut next);
+    }
+    has_state(current, pattern.len())
+}
+
+fn has_state(states: &[u64], pi: usize) -> bool {
+    states[pi / 64] & (1 << (pi % 64)) != 0
+}
+
+/// Add state `pi` and every state reachable from it without consuming a
+/// character: past a `*`, into each alternative of a group, and from the end of
+/// an alternative to the end of its group.
+fn add_state(pattern: &str, states: &mut [u64], mut pi: usize) {
+    let p = pattern.as_bytes();
+    loop {
+        if has_state(states, pi) {
+            return;
+        }
+        states[pi / 64] |= 1 << (pi % 64);
+        match p.get(pi) {
+            Some(b'*') => pi += 1,
+            Some(b'{') => {
+                let Some(close) = pattern[pi..].find('}').map(|close| pi + close) else {
+                    return;
+                };
+                // Groups hold only literal characters, so an alternative's
+                // first state needs no closure of its own unless it is empty.
+                let mut empty = false;
+                for start in core::iter::once(pi + 1).chain(
+                    pattern[pi + 1..close]
+                        .match_indices(',')
+                        .map(|(i, _)| pi + 2 + i),
+                ) {
+                    if matches!(p[start], b',' | b'}') {
+                        empty = true;
+                    } else {
+                        states[start / 64] |= 1 << (start % 64);
+                    }
+                }
+                if !empty {
+                    return;
+                }
+                pi = close + 1;
+            }
+            Some(b',' | b'}') => match pattern[pi..].find('}') {
+                Some(close) => pi += close + 1,
+                None => return,
+            },
+            _ => return,
+        }
+    }
+}
+
+/// Match a component by trying each alternative of its first group in turn.
+///
+/// Only used without `alloc` for components too long for the inline states;
+/// the time grows with the product of the number of alternatives per group.
+#[cfg(not(feature = "alloc"))]
+fn matches_part_by_alternatives(pattern: &str, name: &str) -> bool {
+    let Some(open) = pattern.find('{') else {
+        return matches_part_without_groups(pattern, name);
+    };
+    let Some(close) = pattern[open..].find('}').map(|close| open + close) else {
+        return false;
+    };
+    pattern[open + 1..close].split(',').any(|alternative| {
+        // Match the prefix, this alternative and the rest separately: the
+        // prefix and the alternative are matched by a group-free pattern of
+        // their own, so only the group choices multiply.
+        (0..=name.len())
+            .filter(|&i| name.is_char_boundary(i))
+            .any(|i| {
+                matches_part_without_groups(&pattern[..open], &name[..i])
+                    && name[i..].strip_prefix(alternative).is_some_and(|tail| {
+                        matches_part_by_alternatives(&pattern[close + 1..], tail)
+                    })
+            })
+    })
+}
+
+/// Returns `true` if `c` is selected by the contents of a `[...]` list.
+///
+/// A `-` between two characters forms a range, unless the character before it
+/// already ends a range; a `-` first or last in the list, or right after a
+/// range, is literal. [`validate_pattern`] applies the same rule.
+fn list_contains(list: &str, c: char) -> bool {
+    let (negated, list) = match list.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, list),
+    };
+    let mut found = false;
+    let mut chars = list.chars();
+    while let Some(start) = chars.next() {
+        let mut lookahead = chars.clone();
+        if lookahead.next() == Some('-') {
+            if let Some(end) = lookahead.next() {
+                // A range `start-end`.
+                chars = lookahead;
+                found |= (start..=end).contains(&c);
+                continue;
+            }
+        }
+        found |= start == c;
+    }
+    found != negated
+}
+
+/// Returns `true` for bytes with special meaning in an address pattern.
+pub(crate) fn is_pattern_byte(b: u8) -> bool {
+    matches!(b, b'?' | b'*' | b'[' | b']' | b'{' | b'}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_address, validate_pattern, Pattern, PatternErrorKind};
+
+    #[test]
+    fn literal_patterns_match_exactly() {
+        assert!(matches_address("/synth/freq", "/synth/freq"));
+        assert!(!matches_address("/synth/freq", "/synth/fre"));
+        assert!(!matches_address("/synth/freq", "/synth/freq/1"));
+        assert!(!matches_address("/synth", "/synth/freq"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(matches_address("/ch/?", "/ch/1"));
+        assert!(!matches_address("/ch/?", "/ch/12"));
+        assert!(!matches_address("/ch/?", "/ch/"));
+    }
+
+    #[test]
+    fn star_matches_within_a_part_only() {
+        assert!(matches_address("/synth/*", "/synth/freq"));
+        assert!(matches_address("/synth/*", "/synth/"));
+        assert!(matches_address("/*/freq", "/synth/freq"));
+        assert!(matches_address("/s*h/f*q", "/synth/freq"));
+        assert!(matches_address("/a**b", "/ab"));
+        assert!(!matches_address("/*", "/synth/freq"));
+        assert!(!matches_address("/s*x", "/synth"));
+    }
+
+    #[test]
+    fn brackets_match_lists_and_ranges() {
+        assert!(matches_address("/ch/[123]", "/ch/2"));
+        assert!(!matches_address("/ch/[123]", "/ch/4"));
+        assert!(matches_address("/ch/[a-c0-9]", "/ch/b"));
+        assert!(matches_address("/ch/[a-c0-9]", "/ch/7"));
+        assert!(!matches_address("/ch/[a-c0-9]", "/ch/d"));
+        assert!(matches_address("/ch/[!a-c]", "/ch/d"));
+        assert!(!matches_address("/ch/[!a-c]", "/ch/a"));
+        // A trailing '-' is literal.
+        assert!(matches_address("/ch/[a-]", "/ch/-"));
+    }
+
+    #[test]
+    fn dash_after_a_range_is_literal() {
+        assert!(validate_pattern("/[a-c-e]").is_ok());
+        assert!(matches_address("/[a-c-e]", "/b"));
+        assert!(matches_address("/[a-c-e]", "/-"));
+        assert!(matches_address("/[a-c-e]", "/e"));
+        assert!(!matches_address("/[a-c-e]", "/d"));
+        // The validator and the matcher agree that `e-g` is the next range.
+        assert!(matches_address("/[a-c-e-g]", "/f"));
+        let err = validate_pattern("/[a-c-z-b]").unwrap_err();
+        assert_eq!(err.kind(), PatternErrorKind::InvalidRange);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn matching_time_does_not_explode() {
+        let name = "a".repeat(200);
+        let address = format!("/{name}");
+
+        let stars = format!("/{}b", "*a".repeat(40));
+        assert!(!matches_address(&stars, &address));
+        assert!(matches_address(&format!("/{}", "*a".repeat(40)), &address));
+
+        let groups = format!("/{}b", "{a,aa}".repeat(40));
+        assert!(!matches_address(&groups, &address));
+        assert!(matches_address(
+            &format!("/{}*", "{a,aa}".repeat(40)),
+            &address
+        ));
+        assert!(matches_address(
+            &format!("/*{}", "{a,aa,}*".repeat(20)),
+            &address
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn groups_combine_with_other_wildcards() {
+        assert!(matches_address("/{a,ab}*{c,}", "/abc"));
+        assert!(matches_address("/{x,y}?[0-9]{,z}", "/yq7z"));
+        assert!(!matches_address("/{x,y}?[0-9]{,z}", "/yq7zz"));
+        assert!(matches_address("/*{freq,amp}", "/oscfreq"));
+        assert!(!matches_address("/{freq,amp}*x", "/ampy"));
+        // Components longer than the inline states.
+        let long = format!("/{}{{b,c}}", "a".repeat(600));
+        assert!(matches_address(&long, &format!("/{}c", "a".repeat(600))));
+        assert!(!matches_address(&long, &format!("/{}d", "a".repeat(600))));
+    }
+
+    #[test]
+    fn braces_match_alternatives() {
+        assert!(matches_address("/synth/{freq,amp}", "/synth/amp"));
+        assert!(!matches_address("/synth/{freq,amp}", "/synth/pan"));
+        assert!(matches_address("/{a,ab}c", "/abc"));
+        assert!(matches_address("/x{,y}", "/x"));
+    }
+
+    #[test]
+    fn pattern_type_wraps_validation_and_matching() {
+        let pattern = Pattern::new("/mixer/*/gain").unwrap();
+        assert_eq!(pattern.as_str(), "/mixer/*/gain");
+        assert!(pattern.matches("/mixer/ch1/gain"));
+        assert!(!pattern.is_literal());
+        assert!(Pattern::new("/mixer/ch1/gain").unwrap().is_literal());
+    }
+
+    #[test]
+    fn validation_reports_kind_and_position() {
+        let cases = [
+            ("synth", PatternErrorKind::MissingLeadingSlash, 0),
+            ("/a b", PatternErrorKind::InvalidCharacter(' '), 2),
+            ("/a#", PatternErrorKind::InvalidCharacter('#'), 2),
+            ("/a,b", PatternErrorKind::InvalidCharacter(','), 2),
+            ("/a[bc", PatternErrorKind::UnclosedBracket, 2),
+            ("/a{b,c", PatternErrorKind::UnclosedBrace, 2),
+            ("/a]", PatternErrorKind::UnmatchedClose(']'), 2),
+            ("/a}", PatternErrorKind::UnmatchedClose('}'), 2),
+            ("/[]", PatternErrorKind::EmptyBracket, 1),
+            ("/[!]", PatternErrorKind::EmptyBracket, 1),
+            ("/[z-a]", PatternErrorKind::InvalidRange, 4),
+            ("/{a,[b]}", PatternErrorKind::NestedGroup('['), 4),
+            ("/[a/b]", PatternErrorKind::NestedGroup('/'), 3),
+        ];
+        for (pattern, kind, position) in cases {
+            let err = validate_pattern(pattern).unwrap_err();
+            assert_eq!((err.kind(), err.position()), (kind, position), "{pattern}");
+            assert!(!matches_address(pattern, "/a"));
+        }
+        assert!(validate_pattern("/a/{b,c}/[!0-9]*/?").is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_display_is_descriptive() {
+        let err = validate_pattern("/a[bc").unwrap_err();
+        assert_eq!(err.to_string(), "unclosed '[' at byte 2");
+    }
+}
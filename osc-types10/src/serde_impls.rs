@@ -0,0 +1,665 @@
+//! `serde` support; the representation is documented at the crate root.
+
+use core::fmt::{self, Write as _};
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use serde::de::{self, Deserialize, Deserializer, EnumAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{OscType, OscTypeBuf, TimeTag};
+
+/// Variant names: the type tags, in variant index order.
+const TAGS: &[&str] = &[
+    "i", "f", "s", "b", "h", "t", "d", "S", "c", "r", "m", "T", "F", "N", "I", "[",
+];
+
+fn variant(tag: char) -> (u32, &'static str) {
+    let index = TAGS
+        .iter()
+        .position(|t| t.starts_with(tag))
+        .expect("known type tag");
+    (index as u32, TAGS[index])
+}
+
+impl Serialize for OscType<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, name) = variant(self.tag());
+        match self {
+            OscType::Int(v) => serializer.serialize_newtype_variant("OscType", index, name, v),
+            OscType::Float(v) => serializer.serialize_newtype_variant("OscType", index, name, v),
+            OscType::String(s) | OscType::Symbol(s) => {
+                serializer.serialize_newtype_variant("OscType", index, name, s)
+            }
+            OscType::Blob(b) => {
+                serializer.serialize_newtype_variant("OscType", index, name, &Blob(b))
+            }
+            OscType::Long(v) => serializer.serialize_newtype_variant("OscType", index, name, v),
+            OscType::Time(t) => serializer.serialize_newtype_variant("OscType", index, name, t),
+            OscType::Double(v) => serializer.serialize_newtype_variant("OscType", index, name, v),
+            OscType::Char(c) => serializer.serialize_newtype_variant("OscType", index, name, c),
+            OscType::Color(c) => serializer.serialize_newtype_variant("OscType", index, name, c),
+            OscType::Midi(m) => serializer.serialize_newtype_variant("OscType", index, name, m),
+            OscType::Bool(_) | OscType::Nil | OscType::Inf => {
+                serializer.serialize_unit_variant("OscType", index, name)
+            }
+            OscType::Array(items) => {
+                serializer.serialize_newtype_variant("OscType", index, name, items)
+            }
+        }
+    }
+}
+
+impl Serialize for OscTypeBuf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            OscTypeBuf::Array(items) => {
+                let (index, name) = variant('[');
+                serializer.serialize_newtype_variant("OscType", index, name, items)
+            }
+            // Only arrays allocate when borrowed.
+            _ => self.as_osc_type().serialize(serializer),
+        }
+    }
+}
+
+/// The variant identifier of a serialized argument.
+struct Tag(char);
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TagVisitor;
+
+        impl Visitor<'_> for TagVisitor {
+            type Value = Tag;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an OSC type tag")
+            }
+
+            fn visit_u64<E: de::Error>(self, index: u64) -> Result<Tag, E> {
+                match TAGS.get(index as usize) {
+                    Some(tag) => self.visit_str(tag),
+                    None => Err(E::invalid_value(de::Unexpected::Unsigned(index), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, tag: &str) -> Result<Tag, E> {
+                match tag.chars().next() {
+                    Some(c) if TAGS.contains(&tag) => Ok(Tag(c)),
+                    _ => Err(E::unknown_variant(tag, TAGS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(TagVisitor)
+    }
+}
+
+/// Read the variant data shared by the borrowed and owned argument types;
+/// `string`, `blob` and `array` handle the variants whose data differs.
+macro_rules! visit_arg {
+    ($data:expr, $ty:ident, $string:ty, $blob:ty, $array:ty) => {{
+        let (Tag(tag), variant) = $data.variant()?;
+        Ok(match tag {
+            'i' => $ty::Int(variant.newtype_variant()?),
+            'f' => $ty::Float(variant.newtype_variant()?),
+            's' => $ty::String(variant.newtype_variant::<$string>()?.into()),
+            'b' => $ty::Blob(variant.newtype_variant::<$blob>()?.0),
+            'h' => $ty::Long(variant.newtype_variant()?),
+            't' => $ty::Time(variant.newtype_variant()?),
+            'd' => $ty::Double(variant.newtype_variant()?),
+            'S' => $ty::Symbol(variant.newtype_variant::<$string>()?.into()),
+            'c' => $ty::Char(variant.newtype_variant()?),
+            'r' => $ty::Color(variant.newtype_variant()?),
+            'm' => $ty::Midi(variant.newtype_variant()?),
+            '[' => $ty::Array(variant.newtype_variant::<$array>()?),
+            _ => {
+                variant.unit_variant()?;
+                match tag {
+                    'T' => $ty::Bool(true),
+                    'F' => $ty::Bool(false),
+                    'N' => $ty::Nil,
+                    _ => $ty::Inf,
+                }
+            }
+        })
+    }};
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for OscType<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArgVisitor<'a>(PhantomData<OscType<'a>>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for ArgVisitor<'a> {
+            type Value = OscType<'a>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an OSC argument")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                visit_arg!(data, OscType, &'a str, BorrowedBlob<'a>, Vec<OscType<'a>>)
+            }
+        }
+
+        deserializer.deserialize_enum("OscType", TAGS, ArgVisitor(PhantomData))
+    }
+}
+
+impl<'de> Deserialize<'de> for OscTypeBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ArgVisitor;
+
+        impl<'de> Visitor<'de> for ArgVisitor {
+            type Value = OscTypeBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an OSC argument")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+                visit_arg!(data, OscTypeBuf, String, OwnedBlob, Vec<OscTypeBuf>)
+            }
+        }
+
+        deserializer.deserialize_enum("OscType", TAGS, ArgVisitor)
+    }
+}
+
+/// Serializes a blob as base64 or bytes.
+struct Blob<'a>(&'a [u8]);
+
+impl Serialize for Blob<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&Base64(self.0))
+        } else {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+}
+
+/// A blob borrowed from the input.
+struct BorrowedBlob<'a>(&'a [u8]);
+
+impl<'de: 'a, 'a> Deserialize<'de> for BorrowedBlob<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlobVisitor<'a>(PhantomData<&'a [u8]>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for BlobVisitor<'a> {
+            type Value = BorrowedBlob<'a>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a borrowed byte array")
+            }
+
+            fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+                Ok(BorrowedBlob(v))
+            }
+        }
+
+        // Human-readable formats carry base64, which has to be decoded.
+        if deserializer.is_human_readable() {
+            return Err(de::Error::custom(
+                "base64 blobs cannot be borrowed, deserialize into OscTypeBuf",
+            ));
+        }
+        deserializer.deserialize_bytes(BlobVisitor(PhantomData))
+    }
+}
+
+/// A blob read from bytes, a byte sequence or base64.
+struct OwnedBlob(Vec<u8>);
+
+impl<'de> Deserialize<'de> for OwnedBlob {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlobVisitor;
+
+        impl<'de> Visitor<'de> for BlobVisitor {
+            type Value = OwnedBlob;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a byte array or a base64 string")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(OwnedBlob(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(OwnedBlob(v))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                decode_base64(v)
+                    .map(OwnedBlob)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(OwnedBlob(bytes))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BlobVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BlobVisitor)
+        }
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Formats bytes as padded standard base64.
+struct Base64<'a>(&'a [u8]);
+
+impl fmt::Display for Base64<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.0.chunks(3) {
+            let bits = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |bits, (i, &b)| bits | (b as u32) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let sextet = (bits >> (18 - 6 * i)) & 0x3f;
+                    f.write_char(BASE64[sextet as usize] as char)?;
+                } else {
+                    f.write_char('=')?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decode padded or unpadded standard base64.
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.as_bytes();
+    let s = s.strip_suffix(b"==").or(s.strip_suffix(b"=")).unwrap_or(s);
+    if s.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.chunks(4) {
+        let mut bits = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let sextet = BASE64.iter().position(|&b| b == c)? as u32;
+            bits |= sextet << (18 - 6 * i);
+        }
+        out.extend_from_slice(&bits.to_be_bytes()[1..chunk.len()]);
+    }
+    Some(out)
+}
+
+impl Serialize for TimeTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            serializer.serialize_newtype_struct("TimeTag", &self.to_bits())
+        } else if self.is_immediately() {
+            serializer.serialize_str("immediately")
+        } else {
+            serializer.collect_str(&Rfc3339(*self))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeTag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TimeTagVisitor;
+
+        impl<'de> Visitor<'de> for TimeTagVisitor {
+            type Value = TimeTag;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an RFC 3339 UTC time, \"immediately\" or a 64-bit NTP time tag")
+            }
+
+            fn visit_u64<E: de::Error>(self, bits: u64) -> Result<TimeTag, E> {
+                Ok(TimeTag::from_bits(bits))
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<TimeTag, D::Error> {
+                u64::deserialize(deserializer).map(TimeTag::from_bits)
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<TimeTag, E> {
+                if v == "immediately" {
+                    return Ok(TimeTag::IMMEDIATELY);
+                }
+                parse_rfc3339(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TimeTagVisitor)
+        } else {
+            deserializer.deserialize_newtype_struct("TimeTag", TimeTagVisitor)
+        }
+    }
+}
+
+/// Seconds between 1900-01-01 and 1970-01-01.
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+
+const FRACTION_DIGITS: u32 = 10;
+
+/// Formats a time tag as an RFC 3339 UTC time.
+struct Rfc3339(TimeTag);
+
+impl fmt::Display for Rfc3339 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unix = ntp_seconds(self.0) - NTP_UNIX_OFFSET;
+        let (days, secs) = (unix.div_euclid(86_400), unix.rem_euclid(86_400));
+        let (year, month, day) = civil_from_days(days);
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        )?;
+
+        // The shortest decimal that still rounds back to the same fraction.
+        let scale = 10u128.pow(FRACTION_DIGITS);
+        let mut digits = ((self.0.fraction() as u128 * scale + (1 << 31)) >> 32) as u64;
+        if digits != 0 {
+            let mut width = FRACTION_DIGITS as usize;
+            while digits % 10 == 0 {
+                digits /= 10;
+                width -= 1;
+            }
+            write!(f, ".{digits:0width$}")?;
+        }
+        f.write_char('Z')
+    }
+}
+
+fn parse_rfc3339(s: &str) -> Option<TimeTag> {
+    fn number(s: &str) -> Option<i64> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        s.parse().ok()
+    }
+
+    let s = s.strip_suffix('Z').or_else(|| s.strip_suffix('z'))?;
+    let (date, time) = s.split_once(['T', 't'])?;
+    let mut date = date.splitn(3, '-');
+    let year = number(date.next().filter(|y| y.len() == 4)?)?;
+    let month = number(date.next().filter(|m| m.len() == 2)?)?;
+    let day = number(date.next().filter(|d| d.len() == 2)?)?;
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time = time.splitn(3, ':');
+    let hour = number(time.next().filter(|h| h.len() == 2)?)?;
+    let minute = number(time.next().filter(|m| m.len() == 2)?)?;
+    let second = number(time.next().filter(|s| s.len() == 2)?)?;
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let mut ntp = NTP_UNIX_OFFSET
+        + days_from_civil(year, month, day) * 86_400
+        + hour * 3600
+        + minute * 60
+        + second;
+    let mut fraction_bits = 0u64;
+    if !fraction.is_empty() {
+        if fraction.len() > 30 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let scale = 10u128.pow(fraction.len() as u32);
+        let value: u128 = fraction.parse().ok()?;
+        fraction_bits = (((value << 32) + scale / 2) / scale) as u64;
+        if fraction_bits == 1 << 32 {
+            ntp += 1;
+            fraction_bits = 0;
+        }
+    }
+
+    // Only times inside the two eras a time tag can express round-trip.
+    let tag = TimeTag::new(ntp.rem_euclid(1 << 32) as u32, fraction_bits as u32);
+    (ntp_seconds(tag) == ntp).then_some(tag)
+}
+
+/// Whole seconds since 1900-01-01, taking the era into account.
+fn ntp_seconds(tag: TimeTag) -> i64 {
+    tag.seconds() as i64 + ((tag.era() as i64) << 32)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bundle, Color, Message, MessageBuf, MidiMessage, OscPacket, OscPacketBuf};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::ToString, vec};
+
+    fn message() -> Message<'static> {
+        Message::new(
+            "/a",
+            vec![
+                OscType::Int(1),
+                OscType::Float(0.5),
+                OscType::String("x"),
+                OscType::Blob(&[0, 1, 2, 255]),
+                OscType::Long(-2),
+                OscType::Time(TimeTag::new(3_913_056_000, 1 << 31)),
+                OscType::Double(1.5),
+                OscType::Symbol("sym"),
+                OscType::Char('c'),
+                OscType::Color(Color::new(1, 2, 3, 4)),
+                OscType::Midi(MidiMessage::new(0, 0x90, 60, 127)),
+                OscType::Bool(true),
+                OscType::Bool(false),
+                OscType::Nil,
+                OscType::Inf,
+                OscType::Array(vec![OscType::Int(2), OscType::Array(vec![])]),
+            ],
+        )
+    }
+
+    #[test]
+    fn json_representation() {
+        let json = serde_json::to_value(message()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "address": "/a",
+                "args": [
+                    {"i": 1},
+                    {"f": 0.5},
+                    {"s": "x"},
+                    {"b": "AAEC/w=="},
+                    {"h": -2},
+                    {"t": "2024-01-01T00:00:00.5Z"},
+                    {"d": 1.5},
+                    {"S": "sym"},
+                    {"c": "c"},
+                    {"r": {"red": 1, "green": 2, "blue": 3, "alpha": 4}},
+                    {"m": {"port": 0, "status": 144, "data1": 60, "data2": 127}},
+                    "T",
+                    "F",
+                    "N",
+                    "I",
+                    {"[": [{"i": 2}, {"[": []}]},
+                ],
+            })
+        );
+        let owned: MessageBuf = serde_json::from_value(json).unwrap();
+        assert_eq!(Message::from(&owned), message());
+    }
+
+    #[test]
+    fn json_packets() {
+        let packet = OscPacket::Bundle(Bundle::new(
+            TimeTag::IMMEDIATELY,
+            vec![OscPacket::Message(Message::new("/b", vec![OscType::Nil]))],
+        ));
+        let json = serde_json::to_string(&packet).unwrap();
+        assert_eq!(
+            json,
+            r#"{"bundle":{"timetag":"immediately","packets":[{"message":{"address":"/b","args":["N"]}}]}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<OscPacket<'_>>(&json).unwrap(),
+            packet
+        );
+        let owned: OscPacketBuf = serde_json::from_str(&json).unwrap();
+        assert_eq!(OscPacket::from(&owned), packet);
+    }
+
+    #[test]
+    fn borrows_strings() {
+        let json = r#"{"address":"/a","args":[{"s":"x"},{"S":"y"}]}"#;
+        let msg: Message<'_> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            msg,
+            Message::new("/a", vec![OscType::String("x"), OscType::Symbol("y")])
+        );
+        assert!(core::ptr::eq(msg.address, &json[12..14]));
+
+        // Escapes and base64 need the owned types.
+        let escaped = r#"{"address":"/\u0061","args":[]}"#;
+        assert!(serde_json::from_str::<Message<'_>>(escaped).is_err());
+        let owned: MessageBuf = serde_json::from_str(escaped).unwrap();
+        assert_eq!(owned.address, "/a");
+        let blob = r#"{"address":"/a","args":[{"b":"AA=="}]}"#;
+        assert!(serde_json::from_str::<Message<'_>>(blob).is_err());
+        let owned: MessageBuf = serde_json::from_str(blob).unwrap();
+        assert_eq!(owned.args, [OscTypeBuf::Blob(vec![0])]);
+    }
+
+    #[test]
+    fn messagepack_borrows_blobs() {
+        let bytes = rmp_serde::to_vec(&message()).unwrap();
+        let msg: Message<'_> = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(msg, message());
+        let owned: MessageBuf = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(Message::from(&owned), message());
+
+        let time = rmp_serde::to_vec(&TimeTag::from_bits(0x1234_5678_9abc_def0)).unwrap();
+        assert_eq!(
+            rmp_serde::from_slice::<u64>(&time).unwrap(),
+            0x1234_5678_9abc_def0
+        );
+    }
+
+    #[test]
+    fn base64() {
+        for (bytes, text) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+        ] {
+            assert_eq!(Base64(bytes).to_string(), text);
+            assert_eq!(decode_base64(text).as_deref(), Some(bytes));
+        }
+        assert_eq!(decode_base64("Zm9vYg").as_deref(), Some(&b"foob"[..]));
+        assert_eq!(decode_base64("Zm9vY"), None);
+        assert_eq!(decode_base64("Zm9*"), None);
+    }
+
+    #[test]
+    fn timetag_strings() {
+        let json = |t: TimeTag| serde_json::to_string(&t).unwrap();
+        assert_eq!(
+            json(TimeTag::new(2_208_988_800, 0)),
+            r#""1970-01-01T00:00:00Z""#
+        );
+        assert_eq!(
+            json(TimeTag::new(3_913_056_000, 1)),
+            r#""2024-01-01T00:00:00.0000000002Z""#
+        );
+        // Era 0 covers 1968 to 2036, era 1 2036 to 2104.
+        assert_eq!(
+            json(TimeTag::new(0x8000_0000, 0)),
+            r#""1968-01-20T03:14:08Z""#
+        );
+        assert_eq!(json(TimeTag::new(0, 0)), r#""2036-02-07T06:28:16Z""#);
+        assert_eq!(
+            json(TimeTag::new(0x7fff_ffff, 0)),
+            r#""2104-02-26T09:42:23Z""#
+        );
+
+        for fraction in [0, 1, 2, 0x8000_0000, 0xffff_ffff, 123_456_789] {
+            let t = TimeTag::new(3_913_056_000, fraction);
+            assert_eq!(serde_json::from_str::<TimeTag>(&json(t)).unwrap(), t);
+        }
+
+        let parse = |s: &str| serde_json::from_str::<TimeTag>(&format!("\"{s}\"")).ok();
+        assert_eq!(parse("immediately"), Some(TimeTag::IMMEDIATELY));
+        assert_eq!(
+            parse("2024-02-29T23:59:59.25z"),
+            Some(TimeTag::new(3_918_239_999, 1 << 30))
+        );
+        assert_eq!(
+            parse("1999-12-31T23:59:59.9999999999999Z"),
+            Some(TimeTag::new(3_155_673_600, 0))
+        );
+        assert_eq!(parse("2023-02-29T00:00:00Z"), None);
+        assert_eq!(parse("2024-01-01T00:00:00+01:00"), None);
+        assert_eq!(parse("1968-01-20T03:14:07Z"), None);
+        assert_eq!(parse("2104-02-26T09:42:24Z"), None);
+        assert_eq!(parse("2024-01-01 00:00:00Z"), None);
+        assert_eq!(
+            serde_json::from_str::<TimeTag>("1").unwrap(),
+            TimeTag::IMMEDIATELY
+        );
+    }
+}
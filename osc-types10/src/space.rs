@@ -0,0 +1,504 @@
+//! Trie container keyed by OSC address components.
+
+use core::ops::Bound;
+
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::address::{validate_address, AddressError};
+use crate::pattern::{is_pattern_byte, matches_part, validate_pattern};
+
+/// A map from OSC addresses to values, stored as a trie of address components.
+///
+/// Addresses are split on `/`, so `/synth/1/freq` is stored under the path
+/// `synth` → `1` → `freq`. Besides exact lookups this allows iterating a whole
+/// subtree and finding every entry matched by an incoming address pattern.
+///
+/// ```
+/// use osc_types10::AddressSpace;
+///
+/// let mut space = AddressSpace::new();
+/// space.insert("/synth/1/freq", 440.0).unwrap();
+/// space.insert("/synth/2/freq", 220.0).unwrap();
+/// space.insert("/mixer/gain", 0.8).unwrap();
+///
+/// assert_eq!(space.get("/synth/2/freq"), Some(&220.0));
+/// let hits: Vec<String> = space.query("/synth/*/freq").map(|(address, _)| address).collect();
+/// assert_eq!(hits, ["/synth/1/freq", "/synth/2/freq"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AddressSpace<T> {
+    root: Node<T>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    children: BTreeMap<String, Node<T>>,
+    value: Option<T>,
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Self {
+            children: BTreeMap::new(),
+            value: None,
+        }
+    }
+}
+
+impl<T> Default for AddressSpace<T> {
+    fn default() -> Self {
+        Self {
+            root: Node::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> AddressSpace<T> {
+    /// Create an empty address space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of addresses holding a value.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no address holds a value.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Store `value` at `address`, returning the value it replaced.
+    ///
+    /// Fails without modifying the space if `address` is not a valid OSC
+    /// method address (see [`Address`](crate::Address)).
+    pub fn insert(&mut self, address: &str, value: T) -> Result<Option<T>, AddressError> {
+        validate_address(address)?;
+        let mut node = &mut self.root;
+        for part in address.split('/') {
+            node = node.children.entry(String::from(part)).or_default();
+        }
+        let old = node.value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        Ok(old)
+    }
+
+    /// Remove and return the value at `address`.
+    ///
+    /// Components left without values or children are pruned.
+    pub fn remove(&mut self, address: &str) -> Option<T> {
+        let parts: Vec<&str> = address.split('/').collect();
+        let value = self.root.remove(&parts);
+        if value.is_some() {
+            self.len -= 1;
+        }
+        value
+    }
+
+    /// The value at `address`.
+    pub fn get(&self, address: &str) -> Option<&T> {
+        self.node(address)?.value.as_ref()
+    }
+
+    /// Mutable access to the value at `address`.
+    pub fn get_mut(&mut self, address: &str) -> Option<&mut T> {
+        let mut node = &mut self.root;
+        for part in address.split('/') {
+            node = node.children.get_mut(part)?;
+        }
+        node.value.as_mut()
+    }
+
+    /// Returns `true` if `address` holds a value.
+    pub fn contains(&self, address: &str) -> bool {
+        self.get(address).is_some()
+    }
+
+    /// Iterate over every address and its value, in lexicographic component order.
+    pub fn iter(&self) -> AddressSpaceIter<'_, T> {
+        AddressSpaceIter {
+            stack: self
+                .root
+                .children
+                .iter()
+                .rev()
+                .map(|(name, node)| (name.clone(), node))
+                .collect(),
+        }
+    }
+
+    /// Iterate over `prefix` and every address below it.
+    ///
+    /// `subtree("/synth")` yields `/synth` itself (if it holds a value) and
+    /// `/synth/1/freq`, but not `/synthesizer`.
+    pub fn subtree(&self, prefix: &str) -> AddressSpaceIter<'_, T> {
+        let root = self.node(prefix).map(|node| (String::from(prefix), node));
+        AddressSpaceIter {
+            stack: root.into_iter().collect(),
+        }
+    }
+
+    /// Iterate over every address matched by the OSC address `pattern`.
+    ///
+    /// See [`Pattern`](crate::Pattern) for the syntax. A malformed pattern
+    /// matches nothing.
+    pub fn query<'s, 'p>(&'s self, pattern: &'p str) -> AddressSpaceQuery<'s, 'p, T> {
+        let mut stack = Vec::new();
+        if validate_pattern(pattern).is_ok() {
+            if let Some(node) = self.root.children.get("") {
+                let mut parts = pattern.split('/');
+                // Every valid pattern starts with '/', so the first part is empty.
+                parts.next();
+                stack.push((String::new(), node, parts));
+            }
+        }
+        AddressSpaceQuery { stack }
+    }
+
+    /// Call `f` with every value whose address is matched by `pattern`.
+    ///
+    /// Unlike [`AddressSpace::query`] this does not allocate. A malformed
+    /// pattern matches nothing.
+    pub fn for_each_match(&self, pattern: &str, mut f: impl FnMut(&T)) {
+        if validate_pattern(pattern).is_ok() {
+            let mut parts = pattern.split('/');
+            parts.next();
+            if let Some(node) = self.root.children.get("") {
+                node.visit(parts, &mut f);
+            }
+        }
+    }
+
+    /// Call `f` with mutable access to every value whose address is matched by `pattern`.
+    ///
+    /// A malformed pattern matches nothing.
+    pub fn for_each_match_mut(&mut self, pattern: &str, mut f: impl FnMut(&mut T)) {
+        if validate_pattern(pattern).is_ok() {
+            let mut parts = pattern.split('/');
+            parts.next();
+            if let Some(node) = self.root.children.get_mut("") {
+                node.visit_mut(parts, &mut f);
+            }
+        }
+    }
+
+    fn node(&self, address: &str) -> Option<&Node<T>> {
+        let mut node = &self.root;
+        for part in address.split('/') {
+            node = node.children.get(part)?;
+        }
+        Some(node)
+    }
+}
+
+/// Call `$f` with the name and every child in `$children` whose name is
+/// matched by the pattern `$part`, borrowing through `$range` and `$iter`.
+///
+/// Literal parts are resolved with a single lookup and `{a,b}` groups with
+/// one lookup per alternative; only parts with wildcards scan all children.
+macro_rules! select_children {
+    ($children:expr, $range:ident, $iter:ident, $part:expr, $f:expr) => {{
+        let part: &str = $part;
+        let mut f = $f;
+        if let Some(alternatives) = lookup_keys(part) {
+            for alternative in alternatives {
+                let key = (Bound::Included(alternative), Bound::Included(alternative));
+                if let Some((name, child)) = $children.$range::<str, _>(key).next() {
+                    f(name, child);
+                }
+            }
+        } else {
+            for (name, child) in $children.$iter() {
+                if matches_part(part, name) {
+                    f(name, child);
+                }
+            }
+        }
+    }};
+}
+
+impl<T> Node<T> {
+    fn remove(&mut self, parts: &[&str]) -> Option<T> {
+        let Some((first, rest)) = parts.split_first() else {
+            return self.value.take();
+        };
+        let child = self.children.get_mut(*first)?;
+        let value = child.remove(rest);
+        if child.value.is_none() && child.children.is_empty() {
+            self.children.remove(*first);
+        }
+        value
+    }
+
+    fn visit<'p, I>(&self, mut parts: I, f: &mut impl FnMut(&T))
+    where
+        I: Iterator<Item = &'p str> + Clone,
+    {
+        let Some(part) = parts.next() else {
+            if let Some(value) = &self.value {
+                f(value);
+            }
+            return;
+        };
+        self.for_each_child(part, |_, child| child.visit(parts.clone(), f));
+    }
+
+    fn visit_mut<'p, I>(&mut self, mut parts: I, f: &mut impl FnMut(&mut T))
+    where
+        I: Iterator<Item = &'p str> + Clone,
+    {
+        let Some(part) = parts.next() else {
+            if let Some(value) = &mut self.value {
+                f(value);
+            }
+            return;
+        };
+        select_children!(
+            self.children,
+            range_mut,
+            iter_mut,
+            part,
+            |_: &String, child: &mut Node<T>| { child.visit_mut(parts.clone(), f) }
+        );
+    }
+
+    /// Call `f` with every child whose name is matched by the pattern `part`.
+    fn for_each_child<'s>(&'s self, part: &str, f: impl FnMut(&'s str, &'s Node<T>)) {
+        select_children!(self.children, range, iter, part, f);
+    }
+}
+
+/// Exact child names to look up for a pattern part, or `None` if it needs a scan.
+///
+/// Literal parts yield themselves; a part consisting of a single `{...}` group
+/// of literals yields each distinct alternative once.
+fn lookup_keys(part: &str) -> Option<impl Iterator<Item = &str> + Clone> {
+    let alternatives = if !part.bytes().any(is_pattern_byte) {
+        // A literal never contains ',', so it yields exactly one key.
+        part
+    } else {
+        let inner = part.strip_prefix('{')?.strip_suffix('}')?;
+        if inner.bytes().any(is_pattern_byte) {
+            return None;
+        }
+        inner
+    };
+    let all = alternatives.split(',');
+    Some(
+        alternatives
+            .split(',')
+            .enumerate()
+            .filter(move |&(i, a)| !all.clone().take(i).any(|b| b == a))
+            .map(|(_, a)| a),
+    )
+}
+
+/// Iterator over addresses and values of an [`AddressSpace`].
+///
+/// Created by [`AddressSpace::iter`] and [`AddressSpace::subtree`].
+#[derive(Debug)]
+pub struct AddressSpaceIter<'s, T> {
+    stack: Vec<(String, &'s Node<T>)>,
+}
+
+impl<'s, T> Iterator for AddressSpaceIter<'s, T> {
+    type Item = (String, &'s T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((address, node)) = self.stack.pop() {
+            for (name, child) in node.children.iter().rev() {
+                self.stack.push((join(&address, name), child));
+            }
+            if let Some(value) = &node.value {
+                return Some((address, value));
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the entries of an [`AddressSpace`] matched by a pattern.
+///
+/// Created by [`AddressSpace::query`].
+#[derive(Debug)]
+pub struct AddressSpaceQuery<'s, 'p, T> {
+    stack: Vec<(String, &'s Node<T>, core::str::Split<'p, char>)>,
+}
+
+impl<'s, 'p, T> Iterator for AddressSpaceQuery<'s, 'p, T> {
+    type Item = (String, &'s T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((address, node, mut parts)) = self.stack.pop() {
+            match parts.next() {
+                Some(part) => {
+                    let first = self.stack.len();
+                    node.for_each_child(part, |name, child| {
+                        self.stack
+                            .push((join(&address, name), child, parts.clone()));
+                    });
+                    // Keep lexicographic order when popping.
+                    self.stack[first..].reverse();
+                }
+                None => {
+                    if let Some(value) = &node.value {
+                        return Some((address, value));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+fn join(address: &str, name: &str) -> String {
+    let mut joined = String::with_capacity(address.len() + 1 + name.len());
+    joined.push_str(address);
+    joined.push('/');
+    joined.push_str(name);
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AddressSpace;
+    use crate::AddressErrorKind;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::String, vec, vec::Vec};
+
+    fn space() -> AddressSpace<i32> {
+        let mut space = AddressSpace::new();
+        space.insert("/synth/1/freq", 1).unwrap();
+        space.insert("/synth/1/amp", 2).unwrap();
+        space.insert("/synth/2/freq", 3).unwrap();
+        space.insert("/synth", 4).unwrap();
+        space.insert("/synthesizer/x", 5).unwrap();
+        space.insert("/mixer/gain", 6).unwrap();
+        space
+    }
+
+    fn addresses<'a>(entries: impl Iterator<Item = (String, &'a i32)>) -> Vec<String> {
+        entries.map(|(address, _)| address).collect()
+    }
+
+    #[test]
+    fn insert_get_and_replace() {
+        let mut space = space();
+
+        assert_eq!(space.len(), 6);
+        assert_eq!(space.get("/synth/1/amp"), Some(&2));
+        assert_eq!(space.get("/synth/1"), None);
+        assert!(space.contains("/synth"));
+        assert_eq!(space.insert("/synth/1/amp", 20), Ok(Some(2)));
+        assert_eq!(space.len(), 6);
+
+        *space.get_mut("/mixer/gain").unwrap() += 1;
+        assert_eq!(space.get("/mixer/gain"), Some(&7));
+    }
+
+    #[test]
+    fn insert_rejects_invalid_addresses() {
+        let mut space = space();
+
+        for address in ["", "synth", "/synth/", "/a//b", "/synth/*", "/a b"] {
+            assert!(space.insert(address, 0).is_err(), "{address:?}");
+        }
+        let err = space.insert("/synth/{1,2}", 0).unwrap_err();
+        assert_eq!(err.kind(), AddressErrorKind::ReservedCharacter('{'));
+        assert_eq!(err.position(), 7);
+        assert_eq!(space.len(), 6);
+        assert_eq!(addresses(space.query("/synth/*/freq")).len(), 2);
+    }
+
+    #[test]
+    fn remove_prunes_empty_components() {
+        let mut space = space();
+
+        assert_eq!(space.remove("/mixer/gain"), Some(6));
+        assert_eq!(space.remove("/mixer/gain"), None);
+        assert_eq!(space.remove("/synth/1"), None);
+        assert_eq!(space.len(), 5);
+        assert!(space.subtree("/mixer").next().is_none());
+        assert_eq!(space.remove("/synth"), Some(4));
+        assert_eq!(space.get("/synth/2/freq"), Some(&3));
+    }
+
+    #[test]
+    fn iter_visits_everything_in_order() {
+        let space = space();
+
+        assert_eq!(
+            addresses(space.iter()),
+            vec![
+                "/mixer/gain",
+                "/synth",
+                "/synth/1/amp",
+                "/synth/1/freq",
+                "/synth/2/freq",
+                "/synthesizer/x",
+            ]
+        );
+    }
+
+    #[test]
+    fn subtree_stays_below_prefix() {
+        let space = space();
+
+        assert_eq!(
+            addresses(space.subtree("/synth")),
+            vec!["/synth", "/synth/1/amp", "/synth/1/freq", "/synth/2/freq"]
+        );
+        assert_eq!(
+            addresses(space.subtree("/synth/1")),
+            vec!["/synth/1/amp", "/synth/1/freq"]
+        );
+        assert!(space.subtree("/nothing").next().is_none());
+    }
+
+    #[test]
+    fn query_yields_matching_entries() {
+        let space = space();
+
+        let hits: Vec<_> = space.query("/synth/*/freq").collect();
+        assert_eq!(
+            hits,
+            vec![
+                (String::from("/synth/1/freq"), &1),
+                (String::from("/synth/2/freq"), &3)
+            ]
+        );
+        assert_eq!(addresses(space.query("/synth*")), vec!["/synth"]);
+        assert_eq!(
+            addresses(space.query("/{synth,mixer}/{1,gain}")),
+            vec!["/mixer/gain"]
+        );
+        assert_eq!(addresses(space.query("/synth/?/{amp,freq}")).len(), 3);
+        assert!(space.query("/synth/[1").next().is_none());
+    }
+
+    #[test]
+    fn for_each_match_mut_updates_values() {
+        let mut space = space();
+
+        space.for_each_match_mut("/synth/[12]/freq", |v| *v *= 10);
+        let mut sum = 0;
+        space.for_each_match("/synth/*/freq", |v| sum += v);
+        assert_eq!(sum, 40);
+
+        space.for_each_match_mut("/{synth,mixer}/{2,2,gain}", |v| *v += 1);
+        assert_eq!(space.get("/synth/2/freq"), Some(&30));
+        space.for_each_match_mut("/{mixer,synth}/{gain,1}/{freq,amp}", |v| *v = 0);
+        assert_eq!(addresses(space.iter().filter(|&(_, v)| *v == 0)).len(), 2);
+        assert_eq!(space.get("/mixer/gain"), Some(&7));
+    }
+}
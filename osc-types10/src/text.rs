@@ -0,0 +1,863 @@
+//! Human-readable text syntax: `Display` and `FromStr` for packets.
+
+use core::fmt::{self, Write as _};
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    AsOscType, Bundle, BundleBuf, Color, Message, MessageBuf, MidiMessage, OscPacket, OscPacketBuf,
+    OscType, OscTypeBuf, TimeTag,
+};
+
+/// Reason a string is not a valid text packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The input ended in the middle of a packet.
+    UnexpectedEnd,
+    /// Something else was found where the named item was expected.
+    Expected(&'static str),
+    /// The type tag string contains a tag that is not an OSC 1.0 type tag.
+    UnknownTypeTag(char),
+    /// The type tag string has a `]` without a matching `[`, or an unclosed `[`.
+    UnbalancedArray,
+    /// A value does not parse as its type tag requires.
+    InvalidValue(char),
+    /// A quoted string or character has an unknown or malformed escape.
+    InvalidEscape,
+    /// Input is left over after a complete packet.
+    TrailingInput,
+    /// Bundles and arrays are nested more than 64 levels deep.
+    TooDeep,
+}
+
+/// Error returned when parsing the text syntax fails.
+///
+/// ```
+/// use osc_types10::{MessageBuf, ParseErrorKind};
+///
+/// let err = "/a ,if 1 x".parse::<MessageBuf>().unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::InvalidValue('f'));
+/// assert_eq!(err.position(), 9);
+/// assert_eq!(err.to_string(), "invalid value for type tag 'f' at byte 9");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    position: usize,
+}
+
+impl ParseError {
+    /// What is wrong with the input.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Byte offset in the input where the problem was detected.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::UnexpectedEnd => f.write_str("unexpected end of input")?,
+            ParseErrorKind::Expected(what) => write!(f, "expected {what}")?,
+            ParseErrorKind::UnknownTypeTag(c) => write!(f, "unknown type tag {c:?}")?,
+            ParseErrorKind::UnbalancedArray => f.write_str("unbalanced array brackets")?,
+            ParseErrorKind::InvalidValue(tag) => write!(f, "invalid value for type tag {tag:?}")?,
+            ParseErrorKind::InvalidEscape => f.write_str("invalid escape sequence")?,
+            ParseErrorKind::TrailingInput => f.write_str("unexpected input after packet")?,
+            ParseErrorKind::TooDeep => f.write_str("nesting too deep")?,
+        }
+        write!(f, " at byte {}", self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for Message<'_> {
+    /// Writes the address, the type tag string and the argument values, e.g.
+    /// `/synth/freq ,fi 440.0 1`.
+    ///
+    /// Arrays nested more than 64 levels deep are written in full, but the text
+    /// does not parse back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_message(f, self.address, &self.args)
+    }
+}
+
+impl fmt::Display for Bundle<'_> {
+    /// Writes `#bundle`, the time tag and the elements in brackets; the
+    /// alternate form (`{:#}`) puts each element on its own indented line.
+    ///
+    /// Bundles and arrays nested more than 64 levels deep are written in full,
+    /// but the text does not parse back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_bundle(f, self.timetag, &self.packets, 0)
+    }
+}
+
+impl fmt::Display for OscPacket<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl fmt::Display for MessageBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_message(f, &self.address, &self.args)
+    }
+}
+
+impl fmt::Display for BundleBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_bundle(f, self.timetag, &self.packets, 0)
+    }
+}
+
+impl fmt::Display for OscPacketBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+impl FromStr for MessageBuf {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Parser::new(s).parse(Parser::message)
+    }
+}
+
+impl FromStr for BundleBuf {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Parser::new(s).parse(Parser::bundle)
+    }
+}
+
+impl FromStr for OscPacketBuf {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Parser::new(s).parse(Parser::packet)
+    }
+}
+
+const BUNDLE: &str = "#bundle";
+
+/// How deeply bundles and arrays may nest in parsed text, counted together;
+/// keeps the recursive parser within a bounded amount of stack.
+const MAX_DEPTH: usize = 64;
+
+/// A message or bundle the writers can format, borrowed or owned.
+trait Packet {
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result;
+}
+
+impl Packet for OscPacket<'_> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        match self {
+            OscPacket::Message(msg) => write_message(f, msg.address, &msg.args),
+            OscPacket::Bundle(b) => write_bundle(f, b.timetag, &b.packets, depth),
+        }
+    }
+}
+
+impl Packet for OscPacketBuf {
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        match self {
+            OscPacketBuf::Message(msg) => write_message(f, &msg.address, &msg.args),
+            OscPacketBuf::Bundle(b) => write_bundle(f, b.timetag, &b.packets, depth),
+        }
+    }
+}
+
+fn write_message(
+    f: &mut fmt::Formatter<'_>,
+    address: &str,
+    args: &[impl AsOscType],
+) -> fmt::Result {
+    write_address(f, address)?;
+    if args.is_empty() {
+        return Ok(());
+    }
+    f.write_str(" ,")?;
+    write_tags(f, args)?;
+    write_values(f, args, true)
+}
+
+/// Write a bundle, whose elements are at `depth + 1`.
+fn write_bundle(
+    f: &mut fmt::Formatter<'_>,
+    timetag: TimeTag,
+    packets: &[impl Packet],
+    depth: usize,
+) -> fmt::Result {
+    let inner = depth + 1;
+    write!(f, "{BUNDLE} {} [", timetag.to_bits())?;
+    for packet in packets {
+        if f.alternate() {
+            f.write_char('\n')?;
+            write_indent(f, inner)?;
+        } else {
+            f.write_char(' ')?;
+        }
+        packet.write(f, inner)?;
+    }
+    if f.alternate() && !packets.is_empty() {
+        f.write_char('\n')?;
+        write_indent(f, depth)?;
+    } else {
+        f.write_char(' ')?;
+    }
+    f.write_char(']')
+}
+
+fn write_indent(f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+    (0..depth).try_for_each(|_| f.write_str("  "))
+}
+
+/// Addresses are written bare unless they could be mistaken for something else.
+fn write_address(f: &mut fmt::Formatter<'_>, address: &str) -> fmt::Result {
+    let bare = address.chars().all(|c| c.is_ascii_graphic() && c != '"')
+        && !address.starts_with(['#', ',', ']'])
+        && !address.is_empty();
+    if bare {
+        f.write_str(address)
+    } else {
+        write_quoted(f, address, '"')
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str, quote: char) -> fmt::Result {
+    f.write_char(quote)?;
+    for c in s.chars() {
+        match c {
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c == quote => write!(f, "\\{c}")?,
+            c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char(quote)
+}
+
+fn write_tags(f: &mut fmt::Formatter<'_>, args: &[impl AsOscType]) -> fmt::Result {
+    for arg in args {
+        f.write_char(arg.tag())?;
+        if let Some(items) = arg.items() {
+            write_tags(f, items)?;
+            f.write_char(']')?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the values of `args`, separated by spaces; `T`, `F`, `N` and `I`
+/// have none. `space` asks for a space before the first value.
+fn write_values(
+    f: &mut fmt::Formatter<'_>,
+    args: &[impl AsOscType],
+    mut space: bool,
+) -> fmt::Result {
+    for arg in args {
+        if matches!(arg.tag(), 'T' | 'F' | 'N' | 'I') {
+            continue;
+        }
+        if space {
+            f.write_char(' ')?;
+        }
+        space = true;
+        if let Some(items) = arg.items() {
+            f.write_char('[')?;
+            write_values(f, items, false)?;
+            f.write_char(']')?;
+            continue;
+        }
+        match arg.as_osc_type() {
+            OscType::Int(v) => write!(f, "{v}")?,
+            // Floats use the shortest form that parses back to the same value;
+            // NaNs other than the standard one spell out their bits.
+            OscType::Float(v) if v.is_nan() && v.to_bits() != f32::NAN.to_bits() => {
+                write!(f, "NaN:{:#x}", v.to_bits())?
+            }
+            OscType::Float(v) => write!(f, "{v:?}")?,
+            OscType::String(s) | OscType::Symbol(s) => write_quoted(f, s, '"')?,
+            OscType::Blob(b) => {
+                f.write_str("0x")?;
+                b.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+            }
+            OscType::Long(v) => write!(f, "{v}")?,
+            OscType::Time(t) => write!(f, "{}", t.to_bits())?,
+            OscType::Double(v) if v.is_nan() && v.to_bits() != f64::NAN.to_bits() => {
+                write!(f, "NaN:{:#x}", v.to_bits())?
+            }
+            OscType::Double(v) => write!(f, "{v:?}")?,
+            OscType::Char(c) => write_quoted(f, c.encode_utf8(&mut [0; 4]), '\'')?,
+            OscType::Color(c) => write!(
+                f,
+                "#{:02x}{:02x}{:02x}{:02x}",
+                c.red, c.green, c.blue, c.alpha
+            )?,
+            OscType::Midi(m) => write!(
+                f,
+                "{:02x}:{:02x}:{:02x}:{:02x}",
+                m.port, m.status, m.data1, m.data2
+            )?,
+            // Carried by the type tag alone; arrays are handled above.
+            OscType::Bool(_) | OscType::Nil | OscType::Inf | OscType::Array(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Recursive-descent parser over the input string.
+struct Parser<'s> {
+    s: &'s str,
+    pos: usize,
+    /// Number of bundles and arrays currently open.
+    depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn new(s: &'s str) -> Self {
+        Self {
+            s,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    /// Parse a bundle or array opened at `at` with `item`, failing past
+    /// [`MAX_DEPTH`].
+    fn nested<T>(
+        &mut self,
+        at: usize,
+        item: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error_at(ParseErrorKind::TooDeep, at));
+        }
+        self.depth += 1;
+        let value = item(self);
+        self.depth -= 1;
+        value
+    }
+
+    /// Run `item` over the whole input.
+    fn parse<T>(mut self, item: fn(&mut Self) -> Result<T, ParseError>) -> Result<T, ParseError> {
+        let value = item(&mut self)?;
+        self.skip_whitespace();
+        if self.pos < self.s.len() {
+            return Err(self.error(ParseErrorKind::TrailingInput));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(kind, self.pos)
+    }
+
+    fn error_at(&self, kind: ParseErrorKind, position: usize) -> ParseError {
+        ParseError { kind, position }
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.s[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip whitespace and fail if nothing follows.
+    fn next_item(&mut self) -> Result<char, ParseError> {
+        self.skip_whitespace();
+        self.peek()
+            .ok_or_else(|| self.error(ParseErrorKind::UnexpectedEnd))
+    }
+
+    /// Take characters up to the next whitespace or one of `stop`.
+    fn token(&mut self, stop: &[char]) -> &'s str {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || stop.contains(&c))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn expect(&mut self, c: char, what: &'static str) -> Result<(), ParseError> {
+        if self.next_item()? != c {
+            return Err(self.error(ParseErrorKind::Expected(what)));
+        }
+        self.pos += c.len_utf8();
+        Ok(())
+    }
+
+    fn packet(&mut self) -> Result<OscPacketBuf, ParseError> {
+        self.next_item()?;
+        if self.at_bundle() {
+            self.bundle().map(OscPacketBuf::Bundle)
+        } else {
+            self.message().map(OscPacketBuf::Message)
+        }
+    }
+
+    fn at_bundle(&self) -> bool {
+        self.rest()
+            .strip_prefix(BUNDLE)
+            .is_some_and(|after| !after.starts_with(|c: char| !c.is_whitespace()))
+    }
+
+    fn bundle(&mut self) -> Result<BundleBuf, ParseError> {
+        self.next_item()?;
+        if !self.at_bundle() {
+            return Err(self.error(ParseErrorKind::Expected("\"#bundle\"")));
+        }
+        self.pos += BUNDLE.len();
+        self.next_item()?;
+        let start = self.pos;
+        let timetag = self
+            .token(&['['])
+            .parse()
+            .map_err(|_| self.error_at(ParseErrorKind::InvalidValue('t'), start))?;
+        self.expect('[', "'['")?;
+        let packets = self.nested(self.pos - 1, |parser| {
+            let mut packets = Vec::new();
+            while parser.next_item()? != ']' {
+                packets.push(parser.packet()?);
+            }
+            Ok(packets)
+        })?;
+        self.pos += 1;
+        Ok(BundleBuf::new(TimeTag::from_bits(timetag), packets))
+    }
+
+    fn message(&mut self) -> Result<MessageBuf, ParseError> {
+        let address = match self.next_item()? {
+            '"' => self.quoted('"')?,
+            '#' | ',' | ']' => return Err(self.error(ParseErrorKind::Expected("an address"))),
+            _ => self.token(&[]).into(),
+        };
+        self.skip_whitespace();
+        if self.peek() != Some(',') {
+            return Ok(MessageBuf::new(address, Vec::new()));
+        }
+        self.pos += 1;
+        let tags_start = self.pos;
+        let tags = self.token(&[]);
+        let mut tags = tags
+            .char_indices()
+            .map(|(i, c)| (tags_start + i, c))
+            .peekable();
+        let args = self.args(&mut tags, None)?;
+        Ok(MessageBuf::new(address, args))
+    }
+
+    /// Parse the values for `tags` up to the end of the type tag string or,
+    /// inside an array opened at `open`, its closing `]`.
+    fn args(
+        &mut self,
+        tags: &mut core::iter::Peekable<impl Iterator<Item = (usize, char)>>,
+        open: Option<usize>,
+    ) -> Result<Vec<OscTypeBuf>, ParseError> {
+        let mut args = Vec::new();
+        while let Some((at, tag)) = tags.next() {
+            let arg = match tag {
+                'T' => OscTypeBuf::Bool(true),
+                'F' => OscTypeBuf::Bool(false),
+                'N' => OscTypeBuf::Nil,
+                'I' => OscTypeBuf::Inf,
+                '[' => self.nested(at, |parser| {
+                    parser.expect('[', "'['")?;
+                    let items = parser.args(tags, Some(at))?;
+                    parser.expect(']', "']'")?;
+                    Ok(OscTypeBuf::Array(items))
+                })?,
+                ']' if open.is_some() => return Ok(args),
+                ']' => return Err(self.error_at(ParseErrorKind::UnbalancedArray, at)),
+                'i' | 'f' | 's' | 'b' | 'h' | 't' | 'd' | 'S' | 'c' | 'r' | 'm' => {
+                    self.value(tag)?
+                }
+                _ => return Err(self.error_at(ParseErrorKind::UnknownTypeTag(tag), at)),
+            };
+            args.push(arg);
+        }
+        match open {
+            Some(at) => Err(self.error_at(ParseErrorKind::UnbalancedArray, at)),
+            None => Ok(args),
+        }
+    }
+
+    fn value(&mut self, tag: char) -> Result<OscTypeBuf, ParseError> {
+        if matches!(self.next_item()?, '[' | ']') {
+            return Err(self.error(ParseErrorKind::InvalidValue(tag)));
+        }
+        let invalid = self.error(ParseErrorKind::InvalidValue(tag));
+        let value = match tag {
+            's' => OscTypeBuf::String(self.quoted_value(tag)?),
+            'S' => OscTypeBuf::Symbol(self.quoted_value(tag)?),
+            'c' => {
+                let s = self.quoted_value(tag)?;
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => OscTypeBuf::Char(c),
+                    _ => return Err(invalid),
+                }
+            }
+            _ => {
+                let token = self.token(&['[', ']']);
+                let value = match tag {
+                    'i' => token.parse().ok().map(OscTypeBuf::Int),
+                    'h' => token.parse().ok().map(OscTypeBuf::Long),
+                    'f' => match token.strip_prefix("NaN:0x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok().map(f32::from_bits),
+                        None => token.parse().ok(),
+                    }
+                    .map(OscTypeBuf::Float),
+                    'd' => match token.strip_prefix("NaN:0x") {
+                        Some(hex) => u64::from_str_radix(hex, 16).ok().map(f64::from_bits),
+                        None => token.parse().ok(),
+                    }
+                    .map(OscTypeBuf::Double),
+                    't' => token
+                        .parse()
+                        .ok()
+                        .map(|bits| OscTypeBuf::Time(TimeTag::from_bits(bits))),
+                    'b' => token
+                        .strip_prefix("0x")
+                        .and_then(parse_hex)
+                        .map(OscTypeBuf::Blob),
+                    'r' => token
+                        .strip_prefix('#')
+                        .and_then(parse_hex)
+                        .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+                        .map(|[r, g, b, a]| OscTypeBuf::Color(Color::new(r, g, b, a))),
+                    _ => token
+                        .split(':')
+                        .map(hex_byte)
+                        .collect::<Option<Vec<_>>>()
+                        .and_then(|bytes| <[u8; 4]>::try_from(bytes).ok())
+                        .map(|[port, status, data1, data2]| {
+                            OscTypeBuf::Midi(MidiMessage::new(port, status, data1, data2))
+                        }),
+                };
+                value.ok_or(invalid)?
+            }
+        };
+        // Values must be separated from what follows.
+        match self.peek() {
+            Some(c) if !c.is_whitespace() && c != '[' && c != ']' => {
+                Err(self.error(ParseErrorKind::Expected("whitespace")))
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// A quoted string value for `tag`, quoted with `'` for characters.
+    fn quoted_value(&mut self, tag: char) -> Result<String, ParseError> {
+        let quote = if tag == 'c' { '\'' } else { '"' };
+        if self.peek() != Some(quote) {
+            return Err(self.error(ParseErrorKind::InvalidValue(tag)));
+        }
+        self.quoted(quote)
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String, ParseError> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let escape_at = self.pos;
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error(ParseErrorKind::UnexpectedEnd))?;
+            self.pos += c.len_utf8();
+            match c {
+                c if c == quote => return Ok(out),
+                '\\' => {
+                    let invalid = self.error_at(ParseErrorKind::InvalidEscape, escape_at);
+                    let escaped = self.peek().ok_or(invalid)?;
+                    self.pos += escaped.len_utf8();
+                    out.push(match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        '\\' | '"' | '\'' => escaped,
+                        'u' => {
+                            let hex = self
+                                .rest()
+                                .strip_prefix('{')
+                                .and_then(|rest| rest.split_once('}'))
+                                .map(|(hex, _)| hex)
+                                .ok_or(invalid)?;
+                            self.pos += hex.len() + 2;
+                            u32::from_str_radix(hex, 16)
+                                .ok()
+                                .filter(|_| !hex.is_empty() && hex.len() <= 6)
+                                .and_then(char::from_u32)
+                                .ok_or(invalid)?
+                        }
+                        _ => return Err(invalid),
+                    });
+                }
+                c => out.push(c),
+            }
+        }
+    }
+}
+
+/// Parse pairs of hex digits.
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(hex_byte))
+        .collect()
+}
+
+/// Parse exactly two hex digits.
+fn hex_byte(hex: &str) -> Option<u8> {
+    if hex.len() != 2 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u8::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{osc, osc_bundle};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::ToString, vec};
+
+    fn every_type() -> Message<'static> {
+        osc!(
+            "/all",
+            1,
+            440.0f32,
+            "tab\there \"q\"",
+            &[0x00u8, 0xab, 0xff][..],
+            -5i64,
+            TimeTag::from_bits(0x83aa_7e80_8000_0000),
+            0.1f64,
+            OscType::Symbol("sym"),
+            '\'',
+            Color::new(1, 2, 3, 255),
+            MidiMessage::new(0, 0x90, 60, 127),
+            true,
+            false,
+            OscType::Nil,
+            OscType::Inf,
+            vec![OscType::Int(2), OscType::Array(vec![]), OscType::Nil],
+        )
+    }
+
+    const EVERY_TYPE: &str = "/all ,ifsbhtdScrmTFNI[i[]N] 1 440.0 \"tab\\there \\\"q\\\"\" \
+                              0x00abff -5 9487534655377768448 0.1 \"sym\" '\\'' #010203ff \
+                              00:90:3c:7f [2 []]";
+
+    #[test]
+    fn displays_every_type() {
+        assert_eq!(every_type().to_string(), EVERY_TYPE);
+        assert_eq!(osc!("/a").to_string(), "/a");
+        assert_eq!(
+            osc!("/synth/freq", 440.0f32, 1).to_string(),
+            "/synth/freq ,fi 440.0 1"
+        );
+    }
+
+    #[test]
+    fn parses_what_it_displays() {
+        let msg: MessageBuf = EVERY_TYPE.parse().unwrap();
+        assert_eq!(Message::from(&msg), every_type());
+        assert_eq!(
+            "/a".parse::<MessageBuf>().unwrap(),
+            MessageBuf::new("/a", vec![])
+        );
+        assert_eq!(
+            "  /a ,  ".parse::<MessageBuf>().unwrap(),
+            MessageBuf::new("/a", vec![])
+        );
+    }
+
+    #[test]
+    fn bundles() {
+        let bundle = osc_bundle!(
+            TimeTag::IMMEDIATELY;
+            osc!("/a", 1),
+            osc_bundle!(TimeTag::from_bits(2); osc!("/b")),
+            osc_bundle!(TimeTag::from_bits(3)),
+        );
+        let text = "#bundle 1 [ /a ,i 1 #bundle 2 [ /b ] #bundle 3 [ ] ]";
+        assert_eq!(bundle.to_string(), text);
+        assert_eq!(
+            format!("{bundle:#}"),
+            "#bundle 1 [\n  /a ,i 1\n  #bundle 2 [\n    /b\n  ]\n  #bundle 3 [ ]\n]"
+        );
+        for text in [text.into(), format!("{bundle:#}")] {
+            let parsed: BundleBuf = text.parse().unwrap();
+            assert_eq!(Bundle::from(&parsed), bundle);
+            let packet: OscPacketBuf = text.parse().unwrap();
+            assert_eq!(OscPacket::from(&packet), OscPacket::Bundle(bundle.clone()));
+        }
+    }
+
+    #[test]
+    fn floats_round_trip_exactly() {
+        let odd_nan = f32::from_bits(0x7fc0_0001);
+        let msg = osc!(
+            "/f",
+            f32::MIN_POSITIVE,
+            -0.0f32,
+            f32::INFINITY,
+            f32::NAN,
+            odd_nan,
+            1e300f64,
+            f64::from_bits(0xfff8_0000_0000_0000),
+        );
+        let text = msg.to_string();
+        assert_eq!(
+            text,
+            "/f ,fffffdd 1.1754944e-38 -0.0 inf NaN NaN:0x7fc00001 1e300 NaN:0xfff8000000000000"
+        );
+        let parsed: MessageBuf = text.parse().unwrap();
+        let bits: Vec<u64> = parsed
+            .args
+            .iter()
+            .map(|arg| match arg {
+                OscTypeBuf::Float(v) => v.to_bits().into(),
+                OscTypeBuf::Double(v) => v.to_bits(),
+                _ => unreachable!(),
+            })
+            .collect();
+        let expected: Vec<u64> = msg
+            .args
+            .iter()
+            .map(|arg| match arg {
+                OscType::Float(v) => v.to_bits().into(),
+                OscType::Double(v) => v.to_bits(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(bits, expected);
+    }
+
+    #[test]
+    fn quotes_unusual_addresses_and_strings() {
+        let msg = Message::new("#odd address", vec![OscType::String("\u{0}é\u{7f}")]);
+        let text = msg.to_string();
+        assert_eq!(text, r##""#odd address" ,s "\u{0}é\u{7f}""##);
+        assert_eq!(text.parse::<MessageBuf>().unwrap(), MessageBuf::from(&msg));
+        assert_eq!(
+            Message::new("", vec![])
+                .to_string()
+                .parse::<MessageBuf>()
+                .unwrap(),
+            MessageBuf::new("", vec![])
+        );
+    }
+
+    #[test]
+    fn limits_nesting_depth() {
+        let mut arg = OscType::Nil;
+        for _ in 0..64 {
+            arg = OscType::Array(vec![arg]);
+        }
+        let msg = Message::new("/a", vec![arg]);
+        let text = msg.to_string();
+        assert_eq!(text.parse::<MessageBuf>().unwrap(), MessageBuf::from(&msg));
+
+        let bundle = Bundle::new(TimeTag::IMMEDIATELY, vec![OscPacket::Message(msg)]);
+        let text = bundle.to_string();
+        assert!(text.starts_with("#bundle 1 [ /a ,[[[[") && text.ends_with("]]]] ]"));
+        let err = text.parse::<BundleBuf>().unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::TooDeep);
+
+        let mut bundle = osc_bundle!(1; osc!("/a"));
+        for _ in 0..63 {
+            bundle = osc_bundle!(1; bundle);
+        }
+        let text = bundle.to_string();
+        assert_eq!(text.parse::<BundleBuf>().unwrap(), BundleBuf::from(&bundle));
+
+        let bundle = osc_bundle!(1; bundle);
+        let text = format!("{bundle:#}");
+        assert!(text.contains(&format!("\n{}/a\n", "  ".repeat(65))));
+        let err = text.parse::<BundleBuf>().unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn reports_errors() {
+        let err = |s: &str| s.parse::<OscPacketBuf>().unwrap_err();
+        let check = |s: &str, kind, position| {
+            let e = err(s);
+            assert_eq!((e.kind(), e.position()), (kind, position), "{s}");
+        };
+        check("", ParseErrorKind::UnexpectedEnd, 0);
+        check("/a ,i", ParseErrorKind::UnexpectedEnd, 5);
+        check("/a ,x 1", ParseErrorKind::UnknownTypeTag('x'), 4);
+        check("/a ,i] 1", ParseErrorKind::UnbalancedArray, 5);
+        check("/a ,[i 1", ParseErrorKind::Expected("'['"), 7);
+        check("/a ,[i [1", ParseErrorKind::UnbalancedArray, 4);
+        check("/a ,i 1.5", ParseErrorKind::InvalidValue('i'), 6);
+        check("/a ,b ab", ParseErrorKind::InvalidValue('b'), 6);
+        check("/a ,b 0xabc", ParseErrorKind::InvalidValue('b'), 6);
+        check("/a ,r #0102", ParseErrorKind::InvalidValue('r'), 6);
+        check("/a ,m 0:90:3c:7f", ParseErrorKind::InvalidValue('m'), 6);
+        check("/a ,c 'ab'", ParseErrorKind::InvalidValue('c'), 6);
+        check("/a ,s abc", ParseErrorKind::InvalidValue('s'), 6);
+        check("/a ,s \"a\\q\"", ParseErrorKind::InvalidEscape, 8);
+        check("/a ,s \"a\\u{d800}\"", ParseErrorKind::InvalidEscape, 8);
+        check("/a ,s \"a", ParseErrorKind::UnexpectedEnd, 8);
+        check("/a ,s \"a\"b", ParseErrorKind::Expected("whitespace"), 9);
+        check("/a ,i 1 2", ParseErrorKind::TrailingInput, 8);
+        check("#bundle x [ ]", ParseErrorKind::InvalidValue('t'), 8);
+        check("#bundle 1 /a", ParseErrorKind::Expected("'['"), 10);
+        check("#bundle 1 [ /a", ParseErrorKind::UnexpectedEnd, 14);
+        check(
+            &"#bundle 1 [ ".repeat(200_000),
+            ParseErrorKind::TooDeep,
+            12 * 64 + 10,
+        );
+        check(
+            &format!("/a ,{0} {0}", "[".repeat(200_000)),
+            ParseErrorKind::TooDeep,
+            4 + 64,
+        );
+        check(
+            &format!("{}/a ,[[ [[", "#bundle 1 [ ".repeat(63)),
+            ParseErrorKind::TooDeep,
+            12 * 63 + 5,
+        );
+        assert_eq!(
+            "/a".parse::<BundleBuf>().unwrap_err().kind(),
+            ParseErrorKind::Expected("\"#bundle\"")
+        );
+        assert_eq!(
+            "#bundle 1 [ ]".parse::<MessageBuf>().unwrap_err().kind(),
+            ParseErrorKind::Expected("an address")
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! OSC time tags (64-bit NTP timestamps).
+
+use core::cmp::Ordering;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds between the NTP prime epoch (1900-01-01) and the Unix epoch (1970-01-01).
+#[cfg(feature = "std")]
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// Length of one NTP era in seconds.
+const ERA_SECONDS: u64 = 1 << 32;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// OSC time tag as defined in OSC 1.0 specification
+///
+/// A 64-bit NTP timestamp: the upper 32 bits count seconds since 1900-01-01 and the
+/// lower 32 bits are fractional seconds. The special value [`TimeTag::IMMEDIATELY`]
+/// (`0x00000000_00000001`) means "process as soon as possible".
+///
+/// The seconds counter wraps on 2036-02-07 (the NTP era rollover). As recommended
+/// by RFC 4330, time tags whose seconds have the most significant bit clear are
+/// interpreted as belonging to era 1 (2036-2104) and all others to era 0
+/// (1968-2036). Conversions and ordering follow that rule, so time tags on either
+/// side of the rollover compare as expected.
+///
+/// ```
+/// use core::time::Duration;
+/// use osc_types10::TimeTag;
+///
+/// let t = TimeTag::new(3_900_000_000, 0);
+/// let later = t + Duration::from_millis(500);
+/// assert_eq!(later.seconds(), 3_900_000_000);
+/// assert_eq!(later.fraction(), 1 << 31);
+/// assert!(TimeTag::IMMEDIATELY < t);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeTag(u64);
+
+impl TimeTag {
+    /// The special time tag meaning "immediately".
+    pub const IMMEDIATELY: TimeTag = TimeTag(1);
+
+    /// Create a time tag from NTP seconds and fractional seconds.
+    pub const fn new(seconds: u32, fraction: u32) -> Self {
+        Self(((seconds as u64) << 32) | fraction as u64)
+    }
+
+    /// Create a time tag from its 64-bit wire representation.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the 64-bit wire representation.
+    pub const fn to_bits(self) -> u64 {
+        self.0
+    }
+
+    /// Seconds since the start of the NTP era.
+    pub const fn seconds(self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
+    /// Fractional seconds in units of 2^-32 seconds.
+    pub const fn fraction(self) -> u32 {
+        self.0 as u32
+    }
+
+    /// Returns `true` if this is the special [`TimeTag::IMMEDIATELY`] value.
+    pub const fn is_immediately(self) -> bool {
+        self.0 == Self::IMMEDIATELY.0
+    }
+
+    /// NTP era this time tag is interpreted in: 0 for 1968-2036, 1 for 2036-2104.
+    ///
+    /// [`TimeTag::IMMEDIATELY`] has the most significant bit clear, so it is in
+    /// era 1 like any other small value.
+    pub const fn era(self) -> u32 {
+        if self.seconds() & 0x8000_0000 != 0 {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Create a time tag from a duration since the NTP prime epoch (1900-01-01).
+    ///
+    /// Durations beyond one NTP era wrap around, as the seconds field does on the wire.
+    /// Sub-nanosecond precision is rounded to the nearest fraction.
+    pub fn from_duration(since_ntp_epoch: Duration) -> Self {
+        let seconds = since_ntp_epoch.as_secs() % ERA_SECONDS;
+        Self::new(
+            seconds as u32,
+            nanos_to_fraction(since_ntp_epoch.subsec_nanos()),
+        )
+    }
+
+    /// Duration since the NTP prime epoch (1900-01-01), taking the era into account.
+    ///
+    /// Time tags in era 1 yield durations longer than 2^32 seconds.
+    ///
+    /// [`TimeTag::IMMEDIATELY`] is not a point in time, but it is converted
+    /// like any other value: as one fraction past the start of era 1, i.e.
+    /// 2036-02-07. Check [`TimeTag::is_immediately`] first if that matters.
+    pub fn to_duration(self) -> Duration {
+        let seconds = self.seconds() as u64 + self.era() as u64 * ERA_SECONDS;
+        Duration::from_secs(seconds) + Duration::from_nanos(fraction_to_nanos(self.fraction()))
+    }
+
+    /// Duration elapsed from `earlier` to `self`, or `None` if `earlier` is later.
+    pub fn duration_since(self, earlier: TimeTag) -> Option<Duration> {
+        let (later, earlier) = (self.to_duration(), earlier.to_duration());
+        later.checked_sub(earlier)
+    }
+
+    /// Returns the current time as a time tag.
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// Key used for ordering: era-adjusted 32.32 fixed point.
+    const fn ordering_key(self) -> u64 {
+        // Flipping the top bit maps era 0 (MSB set) below era 1 (MSB clear).
+        self.0 ^ (1 << 63)
+    }
+}
+
+impl Default for TimeTag {
+    fn default() -> Self {
+        Self::IMMEDIATELY
+    }
+}
+
+impl PartialOrd for TimeTag {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// [`TimeTag::IMMEDIATELY`] sorts before every other time tag; all others are
+/// ordered chronologically across the NTP era rollover.
+impl Ord for TimeTag {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .is_immediately()
+            .cmp(&self.is_immediately())
+            .then_with(|| self.ordering_key().cmp(&other.ordering_key()))
+    }
+}
+
+impl From<u64> for TimeTag {
+    fn from(bits: u64) -> Self {
+        Self(bits)
+    }
+}
+
+impl From<TimeTag> for u64 {
+    fn from(t: TimeTag) -> Self {
+        t.0
+    }
+}
+
+impl PartialEq<u64> for TimeTag {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<TimeTag> for Duration {
+    fn from(t: TimeTag) -> Self {
+        t.to_duration()
+    }
+}
+
+impl From<Duration> for TimeTag {
+    fn from(since_ntp_epoch: Duration) -> Self {
+        Self::from_duration(since_ntp_epoch)
+    }
+}
+
+/// Times before 1970-01-01 are only representable back to the NTP prime epoch
+/// (1900-01-01); earlier times saturate to `TimeTag::new(0, 0)`, which is read
+/// back as the start of era 1 (2036-02-07).
+#[cfg(feature = "std")]
+impl From<SystemTime> for TimeTag {
+    fn from(time: SystemTime) -> Self {
+        let offset = Duration::from_secs(NTP_UNIX_OFFSET);
+        let since_ntp_epoch = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => offset + after,
+            Err(before) => offset.saturating_sub(before.duration()),
+        };
+        Self::from_duration(since_ntp_epoch)
+    }
+}
+
+/// Converts via [`TimeTag::to_duration`], so [`TimeTag::IMMEDIATELY`] becomes
+/// 2036-02-07 rather than the current time; check
+/// [`TimeTag::is_immediately`] before converting if that matters.
+#[cfg(feature = "std")]
+impl From<TimeTag> for SystemTime {
+    fn from(t: TimeTag) -> Self {
+        let since_ntp_epoch = t.to_duration();
+        let offset = Duration::from_secs(NTP_UNIX_OFFSET);
+        match since_ntp_epoch.checked_sub(offset) {
+            Some(after) => UNIX_EPOCH + after,
+            None => UNIX_EPOCH - (offset - since_ntp_epoch),
+        }
+    }
+}
+
+/// Converts a duration to 32.32 fixed point, wrapping whole seconds at the era length.
+fn duration_to_bits(d: Duration) -> u64 {
+    ((d.as_secs() % ERA_SECONDS) << 32) | nanos_to_fraction(d.subsec_nanos()) as u64
+}
+
+impl Add<Duration> for TimeTag {
+    type Output = TimeTag;
+
+    /// Adds a duration, wrapping at the NTP era boundary like the wire format does.
+    fn add(self, rhs: Duration) -> TimeTag {
+        TimeTag(self.0.wrapping_add(duration_to_bits(rhs)))
+    }
+}
+
+impl AddAssign<Duration> for TimeTag {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for TimeTag {
+    type Output = TimeTag;
+
+    /// Subtracts a duration, wrapping at the NTP era boundary like the wire format does.
+    fn sub(self, rhs: Duration) -> TimeTag {
+        TimeTag(self.0.wrapping_sub(duration_to_bits(rhs)))
+    }
+}
+
+impl SubAssign<Duration> for TimeTag {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+fn nanos_to_fraction(nanos: u32) -> u32 {
+    ((((nanos as u64) << 32) + NANOS_PER_SEC / 2) / NANOS_PER_SEC) as u32
+}
+
+fn fraction_to_nanos(fraction: u32) -> u64 {
+    (fraction as u64 * NANOS_PER_SEC + (1 << 31)) >> 32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeTag;
+    use core::time::Duration;
+
+    #[test]
+    fn seconds_and_fraction_split() {
+        let t = TimeTag::new(0x1234_5678, 0x9abc_def0);
+
+        assert_eq!(t.to_bits(), 0x1234_5678_9abc_def0);
+        assert_eq!(t.seconds(), 0x1234_5678);
+        assert_eq!(t.fraction(), 0x9abc_def0);
+        assert_eq!(TimeTag::from(t.to_bits()), t);
+    }
+
+    #[test]
+    fn immediately_is_one_and_sorts_first() {
+        assert_eq!(TimeTag::IMMEDIATELY, 1);
+        assert!(TimeTag::IMMEDIATELY.is_immediately());
+        assert_eq!(TimeTag::default(), TimeTag::IMMEDIATELY);
+        assert!(TimeTag::IMMEDIATELY < TimeTag::new(0x8000_0000, 0));
+        assert!(TimeTag::IMMEDIATELY < TimeTag::new(0, 0));
+    }
+
+    #[test]
+    fn duration_roundtrip() {
+        let d = Duration::new(3_913_056_000, 123_456_789);
+        let t = TimeTag::from_duration(d);
+
+        assert_eq!(t.seconds(), 3_913_056_000);
+        assert_eq!(t.to_duration(), d);
+    }
+
+    #[test]
+    fn era_rollover_orders_chronologically() {
+        let before = TimeTag::new(u32::MAX, 0);
+        let after = before + Duration::from_secs(2);
+
+        assert_eq!(before.era(), 0);
+        assert_eq!(after.era(), 1);
+        assert_eq!(after.seconds(), 1);
+        assert!(before < after);
+        assert_eq!(after.duration_since(before), Some(Duration::from_secs(2)));
+        assert_eq!(before.duration_since(after), None);
+        assert_eq!(after.to_duration().as_secs(), (1u64 << 32) + 1);
+    }
+
+    #[test]
+    fn add_and_sub_duration() {
+        let mut t = TimeTag::new(100, 0);
+        t += Duration::from_millis(250);
+        assert_eq!(t, TimeTag::new(100, 1 << 30));
+
+        t -= Duration::from_secs(1);
+        assert_eq!(t, TimeTag::new(99, 1 << 30));
+        assert_eq!(t - Duration::from_millis(250), TimeTag::new(99, 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn system_time_roundtrip() {
+        use super::NTP_UNIX_OFFSET;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let unix = UNIX_EPOCH + Duration::new(1_700_000_000, 500_000_000);
+        let t = TimeTag::from(unix);
+        assert_eq!(t.seconds() as u64, 1_700_000_000 + NTP_UNIX_OFFSET);
+        assert_eq!(t.fraction(), 1 << 31);
+        assert_eq!(SystemTime::from(t), unix);
+
+        // 2040 lies beyond the era rollover.
+        let future = UNIX_EPOCH + Duration::from_secs(2_208_988_800);
+        let t = TimeTag::from(future);
+        assert_eq!(t.era(), 1);
+        assert_eq!(SystemTime::from(t), future);
+
+        assert_eq!(TimeTag::from(UNIX_EPOCH).seconds() as u64, NTP_UNIX_OFFSET);
+    }
+
+    #[test]
+    fn immediately_converts_as_start_of_era_1() {
+        let t = TimeTag::IMMEDIATELY;
+
+        assert_eq!(t.era(), 1);
+        assert_eq!(t.to_duration(), Duration::from_secs(1 << 32));
+        assert_eq!(
+            TimeTag::new(0, 0).to_duration(),
+            Duration::from_secs(1 << 32)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn immediately_and_pre_1900_map_to_2036() {
+        use super::NTP_UNIX_OFFSET;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let rollover = UNIX_EPOCH + Duration::from_secs((1 << 32) - NTP_UNIX_OFFSET);
+        assert_eq!(SystemTime::from(TimeTag::IMMEDIATELY), rollover);
+
+        let before_1900 = UNIX_EPOCH - Duration::from_secs(NTP_UNIX_OFFSET + 1);
+        let t = TimeTag::from(before_1900);
+        assert_eq!(t, TimeTag::new(0, 0));
+        assert_eq!(SystemTime::from(t), rollover);
+    }
+}